@@ -1,75 +1,532 @@
+use std::env;
 use std::io;
-use crossterm::event::{ self, Event, KeyCode };
-use ratatui::{ backend::Backend, Terminal };
+use std::path::{ Path, PathBuf };
+use std::time::{ Duration, Instant, SystemTime, UNIX_EPOCH };
+use crossterm::event::{ self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind };
+use ratatui::{ backend::Backend, layout::Rect, Terminal };
 
-use crate::display;
-use crate::models::todo::{ Todo };
-use crate::reduce::{ AppState, reduce, Action, parse_command };
+use crate::completion;
+use crate::config::Config;
+use crate::display::{ self, TableHit };
+use crate::editor::{ CommandHistory, Edit, COMMAND_HISTORY_FILE };
+use crate::ipc::{ self, Server };
+use crate::lists::{ self, Lists };
+use crate::notify;
+use crate::output::{ CommandResult, Report };
+use crate::models::history::HistoryEntry;
+use crate::models::todo::{ Todo, TodoFile, Trashed, TODOS_FILE };
+use crate::storage::{ self, Lock, LockError };
+use crate::reduce::{ AppState, reduce, Action, Effect, Focus, View, parse_command_for };
+use crate::undo::Version;
 use crate::utils::OneOrMany;
 
-// Clean event loop
-pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, initial_todos: Vec<Todo>) -> io::Result<()> {
-    let mut state = AppState {
-        todos: initial_todos,
-        input_buffer: String::new(),
-        output_buffer: String::new(),
-        should_quit: false,
+// Builds the starting state for the TUI and the CLI: resolves which list to use,
+// loads it along with its history and the user's config. Problems are reported
+// through `output_buffer` rather than aborting.
+pub fn load_state(file: Option<PathBuf>) -> io::Result<AppState> {
+    let lists = Lists::discover(&env::current_dir()?);
+    let file = storage::resolve(file, lists.project.as_deref());
+
+    let mut messages = Vec::new();
+    // Only the default location picks up a ./todos.json from older versions
+    if file == storage::default_path() {
+        match storage::migrate_legacy(Path::new(TODOS_FILE), &file) {
+            Ok(Some(moved)) => {
+                messages.push(format!("📦 Moved {} to {}", moved.display(), file.display()));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                messages.push(format!("⚠️  Could not move ./{} to {}: {}", TODOS_FILE, file.display(), e));
+            }
+        }
+    }
+
+    let (stored, save_blocked) = match storage::load_or_quarantine(&file) {
+        Ok((stored, warning)) => {
+            messages.extend(warning);
+            (stored, None)
+        }
+        Err(message) => {
+            messages.push(message.clone());
+            (TodoFile::default(), Some(message))
+        }
     };
 
+    let mut state = AppState::new(stored.todos.clone());
+    state.trash = stored.trash.clone();
+    state.saved = stored;
+    state.save_blocked = save_blocked;
+    state.history = HistoryEntry::load_all(&HistoryEntry::path_for(&file));
+    state.lists = Lists { current: lists.name_for(&file), ..lists };
+    state.disk_stamp = storage::stamp(&file);
+    state.file = file;
+
+    match Config::load() {
+        Ok(config) => {
+            match config.cycle() {
+                Some(cycle) => state.cycle = cycle,
+                None => messages.push("⚠️  Phase lengths in the config must be at least a minute and not absurdly long. Using default durations.".to_string()),
+            }
+            state.notify = config.notify;
+            state.trash_days = config.trash_days;
+        }
+        Err(e) => {
+            messages.push(format!("⚠️  {}. Using default durations.", e));
+        }
+    }
+    state.output_buffer = messages.join("\n");
+    Ok(state)
+}
+
+const LOCK_RETRY: Duration = Duration::from_secs(2);
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+// Rows moved by PageUp and PageDown in the table, and lines in the output log
+const TABLE_PAGE: isize = 10;
+const OUTPUT_PAGE: isize = 3;
+
+// Clean event loop
+pub fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut state: AppState) -> io::Result<()> {
+    // Only the TUI uses it, so one-off commands never read or trim it
+    state.command_history = CommandHistory::load(&state.lists.data_dir.join(COMMAND_HISTORY_FILE));
+    let mut claim = Claim::default();
+    let mut clicks = Clicks::default();
     while !state.should_quit {
-        terminal.draw(|f| {
-            display::ui(f, &state.todos, &state.input_buffer, &state.output_buffer)
-        })?;
+        // Logged between steps since each one may replace the last message
+        log_output(&mut state);
+        reduce(&mut state, Action::Tick);
+        run_effects(&mut state);
+        claim.follow(&mut state);
+        log_output(&mut state);
+        reload_if_changed(&mut state);
+        autosave(&mut state);
+        log_output(&mut state);
 
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                let actions = handle_key_event(key.code, &state.input_buffer);
+        if let Some(server) = &mut claim.server {
+            for connection in server.poll() {
+                let report = handle_request(&mut state, &connection.request);
+                connection.reply(&report);
+                log_output(&mut state);
+            }
+        }
 
-                match actions {
-                    OneOrMany::Many(actions) => {
-                        actions.into_iter().for_each(|a| reduce(&mut state, a));
-                    }
-                    OneOrMany::One(a) => {
-                        reduce(&mut state, a);
-                    }
+        let size = terminal.size()?;
+        let areas = display::areas(size, &state);
+        state.table_offset = display::fit_offset(&state, display::rows_height(areas.table));
+        state.log.fit(display::log_height(areas.output));
+        terminal.draw(|f| { display::ui(f, &state) })?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let event = event::read()?;
+        // Only presses count; the mouse moving over the window does nothing
+        let pressed = match &event {
+            Event::Key(_) => true,
+            Event::Mouse(mouse) => matches!(mouse.kind, MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown),
+            _ => false,
+        };
+        if !pressed {
+            continue;
+        }
+        match event {
+            Event::Key(key) => {
+                if dismiss_banner(&mut state, key) {
+                    continue;
+                }
+                if let Some(command) = table_command(key, &state) {
+                    run_command(&mut state, &command);
+                    continue;
                 }
 
+                let actions = handle_key_event(key, &state);
+                // Any other key closes the suggestions, after Esc has seen them
+                if !matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+                    reduce(&mut state, Action::CloseCompletion);
+                }
                 if matches!(key.code, KeyCode::Enter) {
-                    state.input_buffer.clear();
+                    // Cleared before applying so a command can leave new input, e.g. `edit 3`
+                    let input = std::mem::take(&mut state.input_buffer);
+                    state.input_cursor = 0;
+                    reduce(&mut state, Action::RememberCommand(input.clone()));
+                    apply_all(&mut state, input.trim(), actions.into_vec());
+                } else {
+                    actions.into_vec().into_iter().for_each(|a| reduce(&mut state, a));
                 }
             }
+            Event::Mouse(mouse) => {
+                // Worked out against the screen as drawn, so before the banner goes away
+                let input = handle_mouse_event(mouse, &state, size, &mut clicks);
+                reduce(&mut state, Action::DismissBanner);
+                reduce(&mut state, Action::CloseCompletion);
+                reduce(&mut state, input.action);
+                if let Some(command) = input.command {
+                    run_command(&mut state, &command);
+                }
+            }
+            _ => {}
         }
     }
     // save on quit
-    if let Err(e) = Todo::save_all(&state.todos) {
+    run_effects(&mut state);
+    if let Some(reason) = &state.save_blocked {
+        eprintln!("{}", reason);
+    } else if let Err(e) = Todo::save_file(&state.file, &state.todos, &state.trash) {
         eprintln!("Failed to save todos: {}", e);
     }
 
     Ok(())
 }
 
-pub fn handle_key_event(key: KeyCode, current_input: &str) -> OneOrMany<Action> {
-    match key {
-        KeyCode::Esc => OneOrMany::One(Action::Quit),
-        KeyCode::Enter => {
-            let input = current_input.trim();
+// What this TUI holds for the list it has open: the lock that makes it the only
+// writer, and the socket other processes send commands to. Both follow list switches.
+#[derive(Default)]
+struct Claim {
+    file: Option<PathBuf>,
+    lock: Option<Lock>,
+    server: Option<Server>,
+    // Why saves are blocked while another instance holds the lock
+    blocked: Option<String>,
+    retry_at: Option<Instant>,
+}
+
+impl Claim {
+    fn follow(&mut self, state: &mut AppState) {
+        if self.file.as_ref() != Some(&state.file) {
+            *self = Claim { file: Some(state.file.clone()), ..Claim::default() };
+            self.acquire(state);
+        } else if self.retry_at.is_some_and(|at| Instant::now() >= at) {
+            self.acquire(state);
+        }
+    }
+
+    fn acquire(&mut self, state: &mut AppState) {
+        match Lock::acquire(&state.file) {
+            Ok(lock) => {
+                self.lock = Some(lock);
+                self.retry_at = None;
+                if let Some(message) = self.blocked.take() && state.save_blocked.as_ref() == Some(&message) {
+                    state.save_blocked = None;
+                    note(state, "🔓 The other pomonote closed this list. Changes are saved again.");
+                }
+            }
+            Err(e @ LockError::Held(_)) => {
+                // Read-only until the other instance lets go; keep checking
+                if self.blocked.is_none() {
+                    let message = format!("🔒 {} is read-only: {}.", state.file.display(), e);
+                    state.save_blocked.get_or_insert_with(|| message.clone());
+                    note(state, &message);
+                    self.blocked = Some(message);
+                }
+                self.retry_at = Some(Instant::now() + LOCK_RETRY);
+                return;
+            }
+            Err(e) => {
+                // e.g. a filesystem without lock support; carry on unprotected
+                note(state, &format!("⚠️  {}: {}. Another pomonote could overwrite it.", state.file.display(), e));
+            }
+        }
+        match Server::bind(&state.file) {
+            Ok(server) => self.server = Some(server),
+            Err(e) => note(state, &format!("⚠️  Remote commands are off for this list: {}", e)),
+        }
+    }
+}
+
+// Moves the latest message into the output log, stamped with the time
+pub fn log_output(state: &mut AppState) {
+    if state.output_buffer.is_empty() {
+        return;
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let message = std::mem::take(&mut state.output_buffer);
+    state.log.push(now, &message);
+}
+
+// Adds a line to the output without hiding what is already there
+fn note(state: &mut AppState, message: &str) {
+    if !state.output_buffer.is_empty() {
+        state.output_buffer.push('\n');
+    }
+    state.output_buffer.push_str(message);
+}
+
+// Picks up changes other programs made to the list since it was last loaded or
+// saved. Unsaved local changes are merged in rather than thrown away.
+pub fn reload_if_changed(state: &mut AppState) {
+    let stamp = storage::stamp(&state.file);
+    // A deleted file is recreated by the next save rather than emptying the screen
+    if stamp.is_none() || stamp == state.disk_stamp {
+        return;
+    }
+    state.disk_stamp = stamp;
+    let disk = match Todo::load_file(&state.file) {
+        Ok(todos) => todos,
+        Err(e) => {
+            state.output_buffer = format!(
+                "⚠️  {} changed on disk but could not be read ({}). Keeping the list on screen.",
+                state.file.display(),
+                e
+            );
+            return;
+        }
+    };
+    state.history = HistoryEntry::load_all(&HistoryEntry::path_for(&state.file));
+    // Snapshots from before the reload would quietly drop the other program's changes
+    state.undo.clear();
+    let base = std::mem::replace(&mut state.saved, disk.clone());
+    if state.dirty {
+        state.todos = Todo::merge(&base.todos, disk.todos, &state.todos);
+        state.trash = Trashed::merge(&base.trash, disk.trash, &state.trash);
+        state.output_buffer = format!("🔄 {} changed on disk. Merged it with your unsaved changes.", state.file.display());
+    } else {
+        state.todos = disk.todos;
+        state.trash = disk.trash;
+        state.output_buffer = format!("🔄 Reloaded {}: it was changed outside this window.", state.file.display());
+    }
+}
+
+// Applies a request from another process as if it had been typed in, and
+// reports back the same way `pomonote --format json` does. Effects run first
+// so a failure to record history is part of the reply.
+pub fn handle_request(state: &mut AppState, request: &str) -> Report {
+    let actions = match ipc::parse_request(request, &state.todos) {
+        Ok(actions) => actions,
+        Err(message) => {
+            return Report::new(state, Vec::new(), vec![CommandResult::error(message)]);
+        }
+    };
+    let mut results = apply_all(state, request.trim(), actions);
+    state.failed = false;
+    run_effects(state);
+    if state.failed {
+        results.push(CommandResult::error(state.output_buffer.clone()));
+    }
+    Report::new(state, Vec::new(), results)
+}
+
+// Applies the actions of one command and makes them a single undo step,
+// labelled with what was typed. Commands that changed nothing aren't recorded.
+pub fn apply_all(state: &mut AppState, label: &str, actions: Vec<Action>) -> Vec<CommandResult> {
+    let before = actions
+        .iter()
+        .any(Action::is_undoable)
+        .then(|| Version::new(&state.todos, &state.trash));
+    let results: Vec<CommandResult> = actions
+        .into_iter()
+        .map(|action| apply(state, action))
+        .collect();
+    if let Some(before) = before && results.iter().any(|r| r.ok) {
+        state.undo.record(label, before, Version::new(&state.todos, &state.trash));
+    }
+    // Every message, e.g. one per todo in `done 1 2`, not just the last
+    state.output_buffer = results
+        .iter()
+        .map(|r| r.message.as_str())
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    results
+}
+
+// Reduces a single action and captures the message it left in the output
+pub fn apply(state: &mut AppState, action: Action) -> CommandResult {
+    state.failed = false;
+    state.output_buffer.clear();
+    reduce(state, action);
+    let message = state.output_buffer.trim_end().to_string();
+    if state.failed { CommandResult::error(message) } else { CommandResult::success(message) }
+}
+
+// Writes the list after every change so a crash or kill loses at most one action
+pub fn autosave(state: &mut AppState) {
+    if !state.dirty || state.save_blocked.is_some() {
+        return;
+    }
+    match Todo::save_file(&state.file, &state.todos, &state.trash) {
+        Ok(()) => {
+            state.dirty = false;
+            state.disk_stamp = storage::stamp(&state.file);
+            state.saved = TodoFile { todos: state.todos.clone(), trash: state.trash.clone() };
+        }
+        Err(e) => {
+            state.fail(format!("❌ Failed to save todos: {}", e));
+        }
+    }
+}
+
+pub fn run_effects(state: &mut AppState) {
+    for effect in std::mem::take(&mut state.effects) {
+        match effect {
+            Effect::Notify(notification) => notify::dispatch(&state.notify, &notification),
+            Effect::Record(entry) => {
+                if let Err(e) = HistoryEntry::append(&HistoryEntry::path_for(&state.file), &[entry]) {
+                    state.fail(format!("❌ Failed to record history: {}", e));
+                }
+            }
+            Effect::SaveCommand(command) => {
+                let path = state.lists.data_dir.join(COMMAND_HISTORY_FILE);
+                if let Err(e) = CommandHistory::append(&path, &command) {
+                    note(state, &format!("⚠️  Could not save command history: {}", e));
+                }
+            }
+            Effect::ShowLists => lists::show(state),
+            Effect::SwitchList { name, create } => lists::switch(state, &name, create),
+            Effect::InitProjectList => {
+                match std::env::current_dir() {
+                    Ok(cwd) => lists::init_project(state, &cwd),
+                    Err(e) => {
+                        state.fail(format!("❌ Failed to read current directory: {}", e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Maps a key to actions. In the input, keys edit the line like readline; in
+// the table, they move the cursor (see `table_command` for the rest).
+// Any key closes a notification and still does its job, except Esc, which only
+// closes it so it doesn't quit by accident. Returns whether the key is used up.
+pub fn dismiss_banner(state: &mut AppState, key: KeyEvent) -> bool {
+    if state.banner.is_none() {
+        return false;
+    }
+    reduce(state, Action::DismissBanner);
+    key.code == KeyCode::Esc
+}
+
+pub fn handle_key_event(key: KeyEvent, state: &AppState) -> OneOrMany<Action> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let edit = |edit| OneOrMany::One(Action::EditInput(edit));
+    match (state.focus, key.code) {
+        (_, KeyCode::Char('z')) if ctrl => OneOrMany::One(Action::Undo),
+        (_, KeyCode::Char('y')) if ctrl => OneOrMany::One(Action::Redo),
+        (Focus::Input, KeyCode::Tab) if state.completion.is_some() => OneOrMany::One(Action::CycleCompletion(1)),
+        (Focus::Input, KeyCode::BackTab) if state.completion.is_some() => OneOrMany::One(Action::CycleCompletion(-1)),
+        // With nothing typed there is nothing to complete, so Tab moves to the table
+        (Focus::Input, KeyCode::Tab) if !state.input_buffer.trim().is_empty() => {
+            OneOrMany::One(Action::Complete(completion::complete(&state.input_buffer, state.input_cursor, state)))
+        }
+        (_, KeyCode::Tab | KeyCode::BackTab) => OneOrMany::One(Action::ToggleFocus),
+        (Focus::Table, KeyCode::Down | KeyCode::Char('j')) if !ctrl => OneOrMany::One(Action::SelectNext),
+        (Focus::Table, KeyCode::Up | KeyCode::Char('k')) if !ctrl => OneOrMany::One(Action::SelectPrevious),
+        (Focus::Table, KeyCode::PageDown) => OneOrMany::One(Action::ScrollTable(TABLE_PAGE)),
+        (Focus::Table, KeyCode::PageUp) => OneOrMany::One(Action::ScrollTable(-TABLE_PAGE)),
+        (Focus::Table, KeyCode::Home | KeyCode::Char('g')) if !ctrl => OneOrMany::One(Action::SelectRow(0)),
+        (Focus::Table, KeyCode::End | KeyCode::Char('G')) if !ctrl => {
+            OneOrMany::One(Action::SelectRow(state.todos.len().saturating_sub(1)))
+        }
+        (Focus::Table, KeyCode::Esc) => OneOrMany::One(Action::FocusInput),
+        (Focus::Table, _) => OneOrMany::One(Action::NoOp),
+        (Focus::Input, KeyCode::Esc) if state.completion.is_some() => OneOrMany::One(Action::CloseCompletion),
+        (Focus::Input, KeyCode::Esc) => OneOrMany::One(Action::Quit),
+        (Focus::Input, KeyCode::Enter) => {
+            let input = state.input_buffer.trim();
             if !input.is_empty() {
-                let actions = parse_command(input);
-                actions
+                parse_command_for(input, &state.todos)
             } else {
                 OneOrMany::One(Action::NoOp)
             }
         }
-        KeyCode::Char(c) => {
-            let mut new_input = current_input.to_string();
-            new_input.push(c);
-            OneOrMany::One(Action::UpdateInput(new_input))
-        }
-        KeyCode::Backspace => {
-            let mut new_input = current_input.to_string();
-            new_input.pop();
-            OneOrMany::One(Action::UpdateInput(new_input))
-        }
+        (Focus::Input, KeyCode::PageUp) => OneOrMany::One(Action::ScrollOutput(OUTPUT_PAGE)),
+        (Focus::Input, KeyCode::PageDown) => OneOrMany::One(Action::ScrollOutput(-OUTPUT_PAGE)),
+        (Focus::Input, KeyCode::Up) => OneOrMany::One(Action::RecallOlder),
+        (Focus::Input, KeyCode::Down) => OneOrMany::One(Action::RecallNewer),
+        (Focus::Input, KeyCode::Left) if ctrl || alt => edit(Edit::WordLeft),
+        (Focus::Input, KeyCode::Right) if ctrl || alt => edit(Edit::WordRight),
+        (Focus::Input, KeyCode::Char('b')) if alt => edit(Edit::WordLeft),
+        (Focus::Input, KeyCode::Char('f')) if alt => edit(Edit::WordRight),
+        (Focus::Input, KeyCode::Char('a')) if ctrl => edit(Edit::Home),
+        (Focus::Input, KeyCode::Char('e')) if ctrl => edit(Edit::End),
+        (Focus::Input, KeyCode::Char('w')) if ctrl => edit(Edit::DeleteWordBack),
+        (Focus::Input, KeyCode::Char('u')) if ctrl => edit(Edit::DeleteToStart),
+        (Focus::Input, KeyCode::Char('k')) if ctrl => edit(Edit::DeleteToEnd),
+        (Focus::Input, KeyCode::Char(_)) if ctrl || alt => OneOrMany::One(Action::NoOp),
+        (Focus::Input, KeyCode::Char(c)) => edit(Edit::Insert(c)),
+        (Focus::Input, KeyCode::Backspace) => edit(Edit::Backspace),
+        (Focus::Input, KeyCode::Delete) => edit(Edit::Delete),
+        (Focus::Input, KeyCode::Left) => edit(Edit::Left),
+        (Focus::Input, KeyCode::Right) => edit(Edit::Right),
+        (Focus::Input, KeyCode::Home) => edit(Edit::Home),
+        (Focus::Input, KeyCode::End) => edit(Edit::End),
         _ => OneOrMany::One(Action::NoOp),
     }
 }
+
+// The command a single key in the focused table runs on the cursor row,
+// e.g. `s` on todo 3 is `start 3`. Going through the parser keeps these keys
+// in step with typed commands, including undo.
+pub fn table_command(key: KeyEvent, state: &AppState) -> Option<String> {
+    if state.focus != Focus::Table || state.view != View::Todos || key.modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+    let id = state.selected_todo()?.id;
+    match key.code {
+        KeyCode::Char(' ') => Some(id.to_string()),
+        KeyCode::Char('s') => Some(format!("start {}", id)),
+        KeyCode::Char('d') => Some(format!("done {}", id)),
+        KeyCode::Char('x') => Some(format!("rm {}", id)),
+        KeyCode::Char('e') | KeyCode::Enter => Some(format!("edit {}", id)),
+        _ => None,
+    }
+}
+
+// Runs a command as if it had been typed, e.g. from a table key or a click
+fn run_command(state: &mut AppState, command: &str) {
+    let actions = parse_command_for(command, &state.todos);
+    apply_all(state, command, actions.into_vec());
+}
+
+// Spots double-clicks, which crossterm doesn't report on its own
+#[derive(Default)]
+pub struct Clicks {
+    last: Option<(Instant, usize)>,
+}
+
+impl Clicks {
+    // Whether a click on `row` is the second one in quick succession
+    fn is_double(&mut self, row: usize) -> bool {
+        let now = Instant::now();
+        let double = self.last.is_some_and(|(at, last)| last == row && now.duration_since(at) <= DOUBLE_CLICK);
+        self.last = if double { None } else { Some((now, row)) };
+        double
+    }
+}
+
+// What a mouse event asks for: a change to the view, plus the command for
+// clicks that toggle a todo so they can be undone like typed ones
+pub struct MouseInput {
+    pub action: Action,
+    pub command: Option<String>,
+}
+
+// A click selects the row under it, or focuses the input. Double-clicking a
+// row or clicking its status toggles the todo, and the wheel scrolls the table
+// or the output log, whichever it is over.
+pub fn handle_mouse_event(mouse: MouseEvent, state: &AppState, size: Rect, clicks: &mut Clicks) -> MouseInput {
+    let areas = display::areas(size, state);
+    let position = Rect::new(mouse.column, mouse.row, 1, 1);
+    let on_table = state.view == View::Todos && areas.table.intersects(position);
+    let on_output = areas.output.intersects(position);
+    let only = |action| MouseInput { action, command: None };
+    match mouse.kind {
+        MouseEventKind::ScrollDown if on_table => only(Action::ScrollTable(1)),
+        MouseEventKind::ScrollUp if on_table => only(Action::ScrollTable(-1)),
+        MouseEventKind::ScrollDown if on_output => only(Action::ScrollOutput(-1)),
+        MouseEventKind::ScrollUp if on_output => only(Action::ScrollOutput(1)),
+        MouseEventKind::Down(MouseButton::Left) if areas.input.intersects(position) => only(Action::FocusInput),
+        MouseEventKind::Down(MouseButton::Left) if on_table => {
+            let toggle = |index: usize| state.todos[index].id.to_string();
+            match display::table_hit(state, areas.table, mouse.column, mouse.row) {
+                Some(TableHit::Status(index)) => MouseInput { action: Action::SelectRow(index), command: Some(toggle(index)) },
+                Some(TableHit::Row(index)) => MouseInput {
+                    action: Action::SelectRow(index),
+                    command: clicks.is_double(index).then(|| toggle(index)),
+                },
+                None => only(Action::NoOp),
+            }
+        }
+        _ => only(Action::NoOp),
+    }
+}
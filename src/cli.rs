@@ -0,0 +1,310 @@
+use clap::{ Parser, Subcommand };
+use serde::{ Deserialize, Serialize };
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use crate::app;
+use crate::ipc;
+use crate::models::todo::Todo;
+use crate::output::{ CommandResult, Format, Reply, Report, StatusReport, TodoJson, JSON_VERSION };
+use crate::reduce::{ reduce, Action, AppState, Effect };
+use crate::stats::Stats;
+use crate::status::{ self, Waybar, DEFAULT_TEMPLATE };
+use crate::storage::{ self, Lock, FILE_ENV };
+use crate::utils::parse_duration;
+
+#[derive(Debug, Parser)]
+#[command(name = "pomonote", version, about = "Terminal todo list with a Pomodoro timer")]
+pub struct Cli {
+    /// Todo file to use instead of the one in the data directory
+    #[arg(short, long, env = FILE_ENV, value_name = "PATH", global = true)]
+    pub file: Option<PathBuf>,
+
+    /// Output format for commands; json prints a versioned document for scripts
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    pub format: Format,
+
+    /// Run a single command and exit instead of opening the TUI
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+// Also the wire format for requests sent to a running TUI, e.g.
+// {"command":"start","ids":[3],"duration":3000}
+#[derive(Debug, Clone, PartialEq, Subcommand, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Add a todo
+    Add {
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        text: Vec<String>,
+    },
+    /// Replace a todo's description
+    Edit {
+        id: u32,
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        text: Vec<String>,
+    },
+    /// List todos with their status and timers
+    Ls,
+    /// Mark todos as completed
+    #[command(alias = "complete")]
+    #[serde(alias = "complete")]
+    Done {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Start the Pomodoro timer for todos
+    Start {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+        /// Length of this work block, e.g. 50m or 1h
+        #[arg(short, long, value_parser = duration_arg)]
+        #[serde(default)]
+        duration: Option<u64>,
+    },
+    /// Stop timers and move todos back to pending
+    Stop {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Pause running timers
+    Pause {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Resume paused timers
+    Resume {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Move todos to the trash
+    #[command(alias = "remove")]
+    #[serde(alias = "remove")]
+    Rm {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Show removed todos
+    Trash,
+    /// Bring todos back from the trash
+    Restore {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Delete everything in the trash for good
+    EmptyTrash,
+    /// Cycle todos through pending, in progress and completed
+    Toggle {
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+    /// Show a summary of finished pomodoros
+    Stats,
+    /// Print a one-line countdown for tmux, shell prompts or status bars
+    Status {
+        /// Placeholders: {icon} {remaining} {seconds} {description} {id} {phase} {round}
+        #[arg(short, long, default_value = DEFAULT_TEMPLATE)]
+        #[serde(default)]
+        template: String,
+        /// Text to print when no timer is running
+        #[arg(short, long, default_value = "")]
+        #[serde(default)]
+        empty: String,
+        /// Print waybar custom module JSON instead of plain text
+        #[arg(long)]
+        #[serde(default)]
+        waybar: bool,
+    },
+}
+
+fn duration_arg(value: &str) -> Result<u64, String> {
+    parse_duration(value).ok_or_else(|| format!("invalid duration '{}', try 25m or 1h30m", value))
+}
+
+impl Command {
+    // The reducer actions this command stands for; empty for read-only commands
+    pub fn actions(&self) -> Vec<Action> {
+        match self {
+            Command::Add { text } => vec![Action::AddTodo(text.join(" "))],
+            Command::Edit { id, text } => vec![Action::EditTodo(*id, text.join(" "))],
+            Command::Done { ids } => ids.iter().copied().map(Action::CompleteTodo).collect(),
+            Command::Start { ids, duration: None } => ids.iter().copied().map(Action::StartTodo).collect(),
+            Command::Start { ids, duration: Some(secs) } =>
+                ids
+                    .iter()
+                    .map(|&id| Action::StartTodoFor(id, *secs))
+                    .collect(),
+            Command::Stop { ids } => ids.iter().copied().map(Action::StopTodo).collect(),
+            Command::Pause { ids } => ids.iter().copied().map(Action::PauseTodo).collect(),
+            Command::Resume { ids } => ids.iter().copied().map(Action::ResumeTodo).collect(),
+            Command::Rm { ids } => ids.iter().copied().map(Action::RemoveTodo).collect(),
+            Command::Toggle { ids } => ids.iter().copied().map(Action::ToggleStatus).collect(),
+            Command::Restore { ids } => ids.iter().copied().map(Action::RestoreTodo).collect(),
+            Command::EmptyTrash => vec![Action::EmptyTrash],
+            Command::Ls | Command::Trash | Command::Stats | Command::Status { .. } => Vec::new(),
+        }
+    }
+}
+
+// Applies `command` to the stored list the same way the TUI would, prints the
+// results and saves. If a TUI has the list open, the change is sent to it
+// instead so it shows up live and isn't overwritten when the TUI saves.
+// Read-only commands only load and print. Fails if any action reported an error.
+pub fn run(mut state: AppState, command: Command, format: Format) -> ExitCode {
+    let warnings: Vec<String> = state.output_buffer
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(String::from)
+        .collect();
+
+    let actions = command.actions();
+    if !actions.is_empty() {
+        let request = serde_json::to_string(&command).expect("commands are always serializable");
+        match ipc::send(&state.file, &request) {
+            Ok(Some(reply)) => {
+                return print_reply(&reply, format);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("❌ Could not reach the running pomonote: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    // Held until we return, so no other instance writes the list in between
+    let lock = (!actions.is_empty()).then(|| Lock::acquire(&state.file));
+    if actions.is_empty() {
+        // Read-only commands just print what was loaded
+        if command == Command::Trash {
+            results.push(app::apply(&mut state, Action::ShowTrash));
+        }
+    } else if let Some(reason) = &state.save_blocked {
+        results.push(CommandResult::error(reason.clone()));
+    } else if let Some(Err(e)) = &lock {
+        results.push(CommandResult::error(format!("🔒 Not changing {}: {}.", state.file.display(), e)));
+    } else {
+        // Another writer may have finished between loading and locking
+        app::reload_if_changed(&mut state);
+        reduce(&mut state, Action::Tick);
+        results.extend(actions.into_iter().map(|action| app::apply(&mut state, action)));
+
+        // Bells and hooks belong to the running TUI, not to one-off commands
+        state.effects.retain(|e| !matches!(e, Effect::Notify(_)));
+        state.failed = false;
+        app::run_effects(&mut state);
+        app::autosave(&mut state);
+        if state.failed {
+            results.push(CommandResult::error(state.output_buffer.clone()));
+        }
+    }
+
+    let ok = results.iter().all(|r| r.ok);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    match format {
+        Format::Text => {
+            warnings.iter().for_each(|w| eprintln!("{}", w));
+            print_results(&results);
+            match command {
+                Command::Ls => print_todos(&state),
+                Command::Stats => println!("{}", Stats::from_history(&state.history, now).summary()),
+                _ => {}
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                stats: matches!(command, Command::Stats).then(|| Stats::from_history(&state.history, now).into()),
+                ..Report::new(&state, warnings, results)
+            };
+            println!("{}", serde_json::to_string(&report).expect("report is always serializable"));
+        }
+    }
+
+    if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+// Prints what a running TUI answered, in the same shape as a local run
+fn print_reply(reply: &str, format: Format) -> ExitCode {
+    let parsed: Reply = match serde_json::from_str(reply) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("❌ Unexpected reply from the running pomonote: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match format {
+        Format::Text => print_results(&parsed.results),
+        Format::Json => println!("{}", reply),
+    }
+    if parsed.ok { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+fn print_results(results: &[CommandResult]) {
+    for result in results {
+        if result.ok {
+            println!("{}", result.message);
+        } else {
+            eprintln!("{}", result.message);
+        }
+    }
+}
+
+// A read-only shortcut for `status`: status bars call it every second or so,
+// so it skips config, history, migration and backups
+pub fn status(file: Option<PathBuf>, command: &Command, format: Format) -> ExitCode {
+    let project = env::current_dir().ok().and_then(|cwd| storage::discover_project(&cwd));
+    let path = storage::resolve(file, project.as_deref());
+    match Todo::load_all(&path) {
+        Ok(todos) => {
+            print_status(&todos, command, format);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("❌ {}: {}", path.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print_status(todos: &[Todo], command: &Command, format: Format) {
+    let Command::Status { template, empty, waybar } = command else {
+        return;
+    };
+    let todo = status::active(todos);
+    let text = todo.map_or_else(|| empty.clone(), |t| status::render(template, t));
+    if *waybar {
+        println!("{}", serde_json::to_string(&Waybar::new(todo, text)).expect("waybar output is always serializable"));
+    } else if format == Format::Json {
+        let report = StatusReport { version: JSON_VERSION, text, todo: todo.map(TodoJson::from) };
+        println!("{}", serde_json::to_string(&report).expect("report is always serializable"));
+    } else if !text.is_empty() {
+        // Idle with no --empty text: print nothing rather than a blank line
+        println!("{}", text);
+    }
+}
+
+fn print_todos(state: &AppState) {
+    if state.todos.is_empty() {
+        println!("No todos in '{}'.", state.lists.current);
+        return;
+    }
+    println!("{:<4} {:<12} {:<12} {:<7} DESCRIPTION", "ID", "STATUS", "PHASE", "TIMER");
+    for todo in &state.todos {
+        let phase = todo.timer.as_ref().map_or(String::new(), |t| t.phase_label(&state.cycle));
+        let timer = todo.timer.as_ref().map_or("--:--".to_string(), |t| {
+            if t.is_paused() { format!("⏸{}", t.output()) } else { t.output() }
+        });
+        println!(
+            "{:<4} {:<12} {:<12} {:<7} {}",
+            todo.id,
+            todo.status.to_string(),
+            phase,
+            timer,
+            todo.description
+        );
+    }
+}
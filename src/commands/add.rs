@@ -1,7 +1,7 @@
 use crate::reduce::AppState;
 use crate::models::todo::Todo;
 
-pub fn run(state: &mut AppState, text: &str) -> () {
+pub fn run(state: &mut AppState, text: &str) {
     let next_id =
         state.todos
             .iter()
@@ -0,0 +1,28 @@
+use crate::reduce::{ AppState, Focus };
+use crate::tokenizer::escape;
+
+pub fn run(state: &mut AppState, id: u32, text: &str) {
+    // Requests from other processes don't go through the parser's check
+    if text.trim().is_empty() {
+        state.fail("Usage: edit <id> [<new text>]".to_string());
+        return;
+    }
+    if let Some(todo) = state.todos.iter_mut().find(|t| t.id == id) {
+        todo.description = text.to_string();
+        state.output_buffer = format!("✏️  Todo {} updated.", id);
+    } else {
+        state.fail(format!("❌ Todo with ID {} not found.", id));
+    }
+}
+
+// Puts `edit <id> <current text>` in the input so it can be changed in place
+pub fn prefill(state: &mut AppState, id: u32) {
+    if let Some(todo) = state.todos.iter().find(|t| t.id == id) {
+        let command = format!("edit {} {}", id, escape(&todo.description));
+        state.set_input(command);
+        state.focus = Focus::Input;
+        state.output_buffer = format!("✏️  Editing todo {}. Change the text and press Enter.", id);
+    } else {
+        state.fail(format!("❌ Todo with ID {} not found.", id));
+    }
+}
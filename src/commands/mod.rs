@@ -1,6 +1,14 @@
 pub mod add;
+pub mod edit;
 pub mod remove;
 pub mod complete;
 pub mod start;
 pub mod stop;
+pub mod pause;
+pub mod resume;
+#[allow(non_snake_case)]
 pub mod toggleStatus;
+pub mod stats;
+pub mod tick;
+pub mod trash;
+pub mod undo;
@@ -1,10 +1,20 @@
 use crate::reduce::AppState;
+use crate::models::todo::{ TodoStatus, Trashed };
+use std::time::{ SystemTime, UNIX_EPOCH };
 
-pub fn run(state: &mut AppState, id: u32) -> () {
+// Moves the todo to the trash, where `restore` can bring it back
+pub fn run(state: &mut AppState, id: u32) {
+    state.record_interruption(id);
     if let Some(pos) = state.todos.iter().position(|t| t.id == id) {
-        state.todos.remove(pos);
-        state.output_buffer = format!("✅ Todo {} removed successfully!", id);
+        let mut todo = state.todos.remove(pos);
+        if todo.status == TodoStatus::InProgress {
+            todo.status = TodoStatus::Pending;
+        }
+        todo.timer = None;
+        let deleted_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        state.trash.push(Trashed { todo, deleted_at });
+        state.output_buffer = format!("🗑️  Todo {} moved to the trash. Use `restore {}` to bring it back.", id, id);
     } else {
-        state.output_buffer = format!("❌ Todo with ID {} not found.", id);
+        state.fail(format!("❌ Todo with ID {} not found.", id));
     }
 }
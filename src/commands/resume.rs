@@ -0,0 +1,20 @@
+use crate::reduce::AppState;
+
+pub fn run(state: &mut AppState, id: u32) {
+    let Some(todo) = state.todos.iter_mut().find(|t| t.id == id) else {
+        state.fail(format!("❌ Todo with ID {} not found.", id));
+        return;
+    };
+    match todo.timer.as_mut() {
+        Some(timer) => {
+            if timer.resume() {
+                state.output_buffer = format!("▶️  Todo {} resumed: {} left.", id, timer.output());
+            } else {
+                state.fail(format!("❌ Todo {} is not paused.", id));
+            }
+        }
+        None => {
+            state.fail(format!("❌ Todo {} has no running timer.", id));
+        }
+    }
+}
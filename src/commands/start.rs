@@ -1,16 +1,26 @@
 use crate::reduce::AppState;
 use crate::models::todo::TodoStatus;
+use crate::models::cycle::Phase;
 use crate::models::timer::Timer;
 
-pub fn run(state: &mut AppState, id: u32) -> () {
+pub fn run(state: &mut AppState, id: u32, duration: Option<u64>) {
+    // Requests from other processes skip parse_duration, which already refuses 0
+    if duration == Some(0) {
+        state.fail("❌ A work block can't be 0 seconds long.".to_string());
+        return;
+    }
+    state.record_interruption(id);
     if let Some(todo) = state.todos.iter_mut().find(|t| t.id == id) {
         todo.status = TodoStatus::InProgress;
-        let mut timer = Timer::new();
+        let mut timer = Timer::for_phase(Phase::Work, 1, &state.cycle);
+        if let Some(duration) = duration {
+            timer = timer.with_duration(duration);
+        }
         timer.start();
         let output = format!("⏱️  Timer started: {}\n", timer.output());
-        state.output_buffer = format!("{}🍅  Focus for 25 minutes!", output);
+        state.output_buffer = format!("{}🍅  Focus for {} minutes!", output, timer.duration() / 60);
         todo.timer = Some(timer);
     } else {
-        state.output_buffer = format!("❌ Todo with ID {} not found.", id);
+        state.fail(format!("❌ Todo with ID {} not found.", id));
     }
 }
@@ -0,0 +1,15 @@
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use crate::reduce::{ AppState, View };
+use crate::stats::Stats;
+
+pub fn run(state: &mut AppState) {
+    state.view = match state.view {
+        View::Todos => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            state.output_buffer = Stats::from_history(&state.history, now).summary();
+            View::Stats
+        }
+        View::Stats => View::Todos,
+    };
+}
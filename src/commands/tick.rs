@@ -0,0 +1,68 @@
+use crate::reduce::{ AppState, Effect };
+use crate::models::cycle::Phase;
+use crate::models::history::HistoryEntry;
+use crate::notify::{ Banner, Notification };
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+pub fn run(state: &mut AppState) {
+    if state.banner.as_ref().is_some_and(|b| b.is_expired()) {
+        state.banner = None;
+    }
+    state.expire_trash(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+
+    let cycle = state.cycle;
+    for index in 0..state.todos.len() {
+        if !state.todos[index].timer.as_ref().is_some_and(|t| t.is_finished()) {
+            continue;
+        }
+        if let Some(entry) = HistoryEntry::finished(&state.todos[index]) {
+            state.record(entry);
+        }
+        let todo = &mut state.todos[index];
+        let Some(timer) = todo.timer.as_mut() else {
+            continue;
+        };
+        let finished = timer.phase();
+        let next = timer.advance(&cycle);
+        let minutes = timer.duration() / 60;
+        let (title, body) = match next {
+            Phase::Work =>
+                (
+                    format!("{} over", finished),
+                    format!(
+                        "🍅 {} over for todo {}. Back to work: round {}/{} ({} min).",
+                        finished,
+                        todo.id,
+                        timer.round(),
+                        cycle.long_break_every,
+                        minutes
+                    ),
+                ),
+            _ =>
+                (
+                    "Work block finished".to_string(),
+                    format!(
+                        "☕ Work block {} done for todo {}. {} for {} min.",
+                        timer.round(),
+                        todo.id,
+                        next,
+                        minutes
+                    ),
+                ),
+        };
+
+        let todo_id = todo.id;
+        state.dirty = true;
+        if state.notify.banner {
+            state.banner = Some(Banner::new(body.clone()));
+        }
+        state.effects.push(
+            Effect::Notify(Notification {
+                todo_id,
+                title,
+                body: body.clone(),
+            })
+        );
+        state.output_buffer = body;
+    }
+}
@@ -1,16 +1,20 @@
 use crate::reduce::AppState;
 use crate::models::todo::TodoStatus;
+use crate::models::cycle::Phase;
 use crate::models::timer::Timer;
 
-pub fn run(state: &mut AppState, id: u32) -> () {
+pub fn run(state: &mut AppState, id: u32) {
+    state.record_interruption(id);
     if let Some(todo) = state.todos.iter_mut().find(|t| t.id == id) {
         match todo.status {
             TodoStatus::Pending => {
                 todo.status = TodoStatus::InProgress;
-                let mut timer = Timer::new();
+                let mut timer = Timer::for_phase(Phase::Work, 1, &state.cycle);
                 timer.start();
                 let output = format!("⏱️  Timer started: {}\n", timer.output());
-                state.output_buffer.push_str(&format!("{}🍅  Focus for 25 minutes!\n", &output));
+                state.output_buffer.push_str(
+                    &format!("{}🍅  Focus for {} minutes!\n", output, timer.duration() / 60)
+                );
                 todo.timer = Some(timer);
             }
             TodoStatus::Completed => {
@@ -20,9 +24,10 @@ pub fn run(state: &mut AppState, id: u32) -> () {
             TodoStatus::InProgress => {
                 todo.timer = None;
                 todo.status = TodoStatus::Completed;
-                let output = format!("✅ Todo {} completed! Great work!\n", id);
-                state.output_buffer.push_str(&format!("{}🍅  Focus for 25 minutes! \n", &output));
+                state.output_buffer.push_str(&format!("✅ Todo {} completed! Great work!\n", id));
             }
         };
+    } else {
+        state.fail(format!("❌ Todo with ID {} not found.", id));
     }
 }
@@ -0,0 +1,52 @@
+use crate::reduce::AppState;
+use crate::models::todo::Todo;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+pub fn show(state: &mut AppState) {
+    if state.trash.is_empty() {
+        state.output_buffer = "🗑️  The trash is empty.".to_string();
+        return;
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let lines: Vec<String> = state.trash
+        .iter()
+        .map(|t| format!("#{} {} (removed {})", t.todo.id, t.todo.description, age(now.saturating_sub(t.deleted_at))))
+        .collect();
+    state.output_buffer = format!("🗑️  Trash:\n{}", lines.join("\n"));
+}
+
+// Puts back the most recently removed todo with `id`. If a new todo has taken
+// the id in the meantime, the restored one gets the next free id.
+pub fn restore(state: &mut AppState, id: u32) {
+    let Some(pos) = state.trash.iter().rposition(|t| t.todo.id == id) else {
+        state.fail(format!("❌ No todo with ID {} in the trash.", id));
+        return;
+    };
+    let mut todo = state.trash.remove(pos).todo;
+    if state.todos.iter().any(|t| t.id == id) {
+        todo.id = Todo::next_id(&state.todos);
+        state.output_buffer = format!("♻️  Todo {} restored as {}: its ID was taken.", id, todo.id);
+    } else {
+        state.output_buffer = format!("♻️  Todo {} restored.", id);
+    }
+    let at = state.todos.partition_point(|t| t.id < todo.id);
+    state.todos.insert(at, todo);
+}
+
+pub fn empty(state: &mut AppState) {
+    let count = state.trash.len();
+    state.trash.clear();
+    state.output_buffer = match count {
+        0 => "🗑️  The trash is already empty.".to_string(),
+        1 => "🗑️  Emptied the trash (1 todo).".to_string(),
+        n => format!("🗑️  Emptied the trash ({} todos).", n),
+    };
+}
+
+fn age(secs: u64) -> String {
+    match secs / 86400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
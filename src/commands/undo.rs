@@ -0,0 +1,19 @@
+use crate::reduce::AppState;
+
+pub fn undo(state: &mut AppState) {
+    match state.undo.undo(&mut state.todos, &mut state.trash) {
+        Some(label) => {
+            state.output_buffer = format!("↩️  Undid '{}'.", label);
+        }
+        None => state.fail("❌ Nothing to undo.".to_string()),
+    }
+}
+
+pub fn redo(state: &mut AppState) {
+    match state.undo.redo(&mut state.todos, &mut state.trash) {
+        Some(label) => {
+            state.output_buffer = format!("↪️  Redid '{}'.", label);
+        }
+        None => state.fail("❌ Nothing to redo.".to_string()),
+    }
+}
@@ -0,0 +1,195 @@
+use crate::reduce::AppState;
+
+// Every command the input understands, aliases included, for completion
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("add", "Add a todo"),
+    ("edit", "Change a todo's text"),
+    ("remove", "Move todos to the trash"),
+    ("rm", "Alias of remove"),
+    ("complete", "Mark todos as done"),
+    ("done", "Alias of complete"),
+    ("start", "Start the timer"),
+    ("stop", "Stop the timer"),
+    ("pause", "Pause the timer"),
+    ("resume", "Resume a paused timer"),
+    ("stats", "Show or hide stats"),
+    ("list", "Show, create or switch lists"),
+    ("trash", "Show removed todos"),
+    ("restore", "Bring todos back from the trash"),
+    ("empty-trash", "Delete the trash for good"),
+    ("undo", "Undo the last command"),
+    ("redo", "Redo the last undone command"),
+    ("quit", "Exit"),
+    ("exit", "Alias of quit"),
+    ("q", "Alias of quit"),
+];
+
+const LIST_COMMANDS: &[(&str, &str)] = &[
+    ("init", "Create a list for this project"),
+    ("new", "Create a list and switch to it"),
+    ("switch", "Switch to another list"),
+];
+
+// Commands whose arguments are todo ids
+const ID_COMMANDS: &[&str] = &["remove", "rm", "complete", "done", "start", "stop", "pause", "resume"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub text: String,
+    // Shown next to `text` in the popup, e.g. a todo's description
+    pub detail: String,
+}
+
+// Suggestions for the word being typed, which starts at `start` (in characters)
+// and ends at the cursor. `selected` is the candidate currently in the input
+// while cycling with Tab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub start: usize,
+    pub candidates: Vec<Candidate>,
+    pub selected: Option<usize>,
+}
+
+impl Completion {
+    // The longest text every candidate starts with
+    pub fn common_prefix(&self) -> String {
+        let Some((first, rest)) = self.candidates.split_first() else {
+            return String::new();
+        };
+        let mut prefix: Vec<char> = first.text.chars().collect();
+        for candidate in rest {
+            let shared = prefix
+                .iter()
+                .zip(candidate.text.chars())
+                .take_while(|(a, b)| **a == *b)
+                .count();
+            prefix.truncate(shared);
+        }
+        prefix.into_iter().collect()
+    }
+}
+
+// What can go where the cursor is: command names first, then depending on the
+// command, todo ids, trashed ids, list names, and `+tags` anywhere
+pub fn complete(input: &str, cursor: usize, state: &AppState) -> Completion {
+    let before: String = input.chars().take(cursor).collect();
+    let word_len = before
+        .chars()
+        .rev()
+        .take_while(|c| !c.is_whitespace())
+        .count();
+    let start = before.chars().count() - word_len;
+    let word: String = before.chars().skip(start).collect();
+    let words: Vec<&str> = before.split_whitespace().collect();
+    // Words before the one being completed
+    let done = if word.is_empty() { &words[..] } else { &words[..words.len() - 1] };
+
+    let candidates = match done {
+        _ if word.starts_with('+') => tags(state),
+        [] => pairs(COMMANDS),
+        ["list"] => pairs(LIST_COMMANDS),
+        ["list", "switch"] =>
+            state.lists
+                .names()
+                .into_iter()
+                .map(|name| Candidate { detail: if name == state.lists.current { "current".to_string() } else { String::new() }, text: name })
+                .collect(),
+        ["edit"] => todo_ids(state),
+        ["restore", ..] =>
+            state.trash
+                .iter()
+                .map(|t| Candidate { text: t.todo.id.to_string(), detail: t.todo.description.clone() })
+                .collect(),
+        [command, ..] if ID_COMMANDS.contains(command) => todo_ids(state),
+        _ => Vec::new(),
+    };
+    let candidates = candidates
+        .into_iter()
+        .filter(|c| c.text.starts_with(&word) && c.text != word)
+        .collect();
+    Completion { start, candidates, selected: None }
+}
+
+fn pairs(pairs: &[(&str, &str)]) -> Vec<Candidate> {
+    pairs
+        .iter()
+        .map(|(text, detail)| Candidate { text: text.to_string(), detail: detail.to_string() })
+        .collect()
+}
+
+fn todo_ids(state: &AppState) -> Vec<Candidate> {
+    state.todos
+        .iter()
+        .map(|t| Candidate { text: t.id.to_string(), detail: t.description.clone() })
+        .collect()
+}
+
+// Tags used in todo descriptions, lowercased like the `+tag` selector, with
+// how many todos have each
+fn tags(state: &AppState) -> Vec<Candidate> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for todo in &state.todos {
+        let mut seen = Vec::new();
+        for tag in todo.description.split_whitespace().filter(|w| w.len() > 1 && w.starts_with('+')) {
+            let tag = tag.to_lowercase();
+            if seen.contains(&tag) {
+                continue;
+            }
+            match counts.iter_mut().find(|(t, _)| *t == tag) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((tag.clone(), 1)),
+            }
+            seen.push(tag);
+        }
+    }
+    counts.sort();
+    counts
+        .into_iter()
+        .map(|(text, count)| Candidate { text, detail: if count == 1 { "1 todo".to_string() } else { format!("{} todos", count) } })
+        .collect()
+}
+
+// Fills in a lone candidate, or as much as all candidates share and opens the popup
+pub fn show(state: &mut AppState, mut completion: Completion) {
+    match completion.candidates.len() {
+        0 => {}
+        1 => {
+            let text = format!("{} ", completion.candidates[0].text);
+            replace_word(state, completion.start, &text);
+        }
+        _ => {
+            replace_word(state, completion.start, &completion.common_prefix());
+            completion.selected = None;
+            state.completion = Some(completion);
+        }
+    }
+}
+
+// Puts the next (or with a negative `step`, previous) candidate in the input
+pub fn cycle(state: &mut AppState, step: isize) {
+    let Some(completion) = &mut state.completion else {
+        return;
+    };
+    let len = completion.candidates.len() as isize;
+    let next = match completion.selected {
+        Some(i) => ((i as isize) + step).rem_euclid(len),
+        None if step < 0 => len - 1,
+        None => 0,
+    } as usize;
+    completion.selected = Some(next);
+    let start = completion.start;
+    let text = completion.candidates[next].text.clone();
+    replace_word(state, start, &text);
+}
+
+// Replaces the input from `start` up to the cursor with `text`
+fn replace_word(state: &mut AppState, start: usize, text: &str) {
+    let chars: Vec<char> = state.input_buffer.chars().collect();
+    let cursor = state.input_cursor.min(chars.len());
+    let start = start.min(cursor);
+    let mut input: String = chars[..start].iter().collect();
+    input.push_str(text);
+    state.input_cursor = start + text.chars().count();
+    input.extend(&chars[cursor..]);
+    state.input_buffer = input;
+}
@@ -0,0 +1,76 @@
+use serde::{ Deserialize, Serialize };
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::cycle::Cycle;
+use crate::models::todo::TRASH_DAYS;
+use crate::notify::NotifyConfig;
+
+// User settings read from $XDG_CONFIG_HOME/pomonote/config.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // Phase lengths in minutes
+    pub work: u64,
+    pub short_break: u64,
+    pub long_break: u64,
+    pub long_break_every: u32,
+    pub notify: NotifyConfig,
+    // Days before removed todos are deleted for good; 0 keeps them
+    pub trash_days: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let cycle = Cycle::default();
+        Self {
+            work: cycle.work / 60,
+            short_break: cycle.short_break / 60,
+            long_break: cycle.long_break / 60,
+            long_break_every: cycle.long_break_every,
+            notify: NotifyConfig::default(),
+            trash_days: TRASH_DAYS,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        let base = env
+            ::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("pomonote").join("config.json"))
+    }
+
+    // A missing file is not an error; a malformed one is reported so the user can fix it
+    pub fn load() -> Result<Config, String> {
+        let Some(path) = Self::path() else {
+            return Ok(Config::default());
+        };
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let data = fs
+            ::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&data).map_err(|e| format!("Invalid config {}: {}", path.display(), e))
+    }
+
+    pub fn parse(data: &str) -> Result<Config, serde_json::Error> {
+        serde_json::from_str(data)
+    }
+
+    // None when a phase is zero or too long to count in seconds
+    pub fn cycle(&self) -> Option<Cycle> {
+        let secs = |minutes: u64| minutes.checked_mul(60).filter(|&secs| secs > 0);
+        Some(Cycle {
+            work: secs(self.work)?,
+            short_break: secs(self.short_break)?,
+            long_break: secs(self.long_break)?,
+            long_break_every: self.long_break_every.max(1),
+        })
+    }
+}
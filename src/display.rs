@@ -1,35 +1,176 @@
 use ratatui::{
-    layout::{ Constraint, Direction, Layout },
-    style::{ Color, Style },
-    widgets::{ Block, Borders, Cell, Paragraph, Row, Table },
+    layout::{ Constraint, Direction, Flex, Layout },
+    layout::{ Margin, Rect },
+    style::{ Color, Modifier, Style },
+    text::{ Line, Span },
+    widgets::{
+        BarChart,
+        Block,
+        Borders,
+        Cell,
+        Clear,
+        HighlightSpacing,
+        List,
+        ListItem,
+        ListState,
+        Paragraph,
+        Row,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        Sparkline,
+        Table,
+        TableState,
+    },
     Frame,
 };
 
-use crate::models::todo::{ Todo, TodoStatus };
+use crate::completion::Completion;
+use crate::log::TIME_WIDTH;
+use crate::models::todo::TodoStatus;
+use crate::notify::Banner;
+use crate::reduce::{ AppState, Focus, View };
+use crate::stats::{ Stats, DAYS_SHOWN };
+use std::time::{ SystemTime, UNIX_EPOCH };
 
-pub fn ui(f: &mut Frame, todos: &[Todo], input_buffer: &str, output_buffer: &str) {
+const TODO_COLUMNS: [Constraint; 5] = [
+    Constraint::Percentage(8),
+    Constraint::Percentage(44),
+    Constraint::Percentage(16),
+    Constraint::Percentage(16),
+    Constraint::Percentage(16),
+];
+const STATUS_COLUMN: usize = 2;
+const HIGHLIGHT_SYMBOL: &str = "▶ ";
+// Border plus the header row and the margin under it
+const TABLE_TOP: u16 = 3;
+// Suggestions shown at once above the input; more scroll
+const COMPLETION_ROWS: u16 = 8;
+const COMPLETION_WIDTH: u16 = 60;
+// Lines of the output log shown at once, inside its border
+const OUTPUT_LINES: u16 = 6;
+
+// Where each part of the screen is, shared by drawing and mouse handling
+pub struct Areas {
+    pub banner: Option<Rect>,
+    pub table: Rect,
+    pub output: Rect,
+    pub input: Rect,
+}
+
+pub fn areas(size: Rect, state: &AppState) -> Areas {
+    let mut area = size;
+    let mut banner = None;
+    if state.banner.is_some() {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+            .split(area);
+        banner = Some(split[0]);
+        area = split[1];
+    }
+
+    // The table gets whatever the output log and the one-line input leave
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Percentage(65),
-                Constraint::Percentage(20),
-                Constraint::Percentage(15),
+                Constraint::Min(TABLE_TOP + 2),
+                Constraint::Length(OUTPUT_LINES + 2),
+                Constraint::Length(3),
             ].as_ref()
         )
-        .split(f.size());
+        .split(area);
+    Areas { banner, table: chunks[0], output: chunks[1], input: chunks[2] }
+}
+
+// What the mouse is over in the todo table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableHit {
+    Row(usize),
+    Status(usize),
+}
+
+fn row_height(description: &str) -> u16 {
+    description
+        .chars()
+        .filter(|c| *c == '\n')
+        .count()
+        .max(1) as u16
+}
 
-    let header_cells = ["ID", "Description", "Status", "Timer"]
+// Lines available for rows inside the table's border and header
+pub fn rows_height(table: Rect) -> u16 {
+    table.height.saturating_sub(TABLE_TOP + 1)
+}
+
+// Lines of the output log that fit inside its border
+pub fn log_height(output: Rect) -> usize {
+    output.height.saturating_sub(2) as usize
+}
+
+// The first row to show: `state.table_offset` moved as little as possible to
+// keep the cursor on screen without leaving space under the last row. Drawing
+// uses it as is, so mouse positions map back to the same rows.
+pub fn fit_offset(state: &AppState, rows_height: u16) -> usize {
+    let heights: Vec<u16> = state.todos
+        .iter()
+        .map(|t| row_height(&t.description))
+        .collect();
+    let Some(selected) = state.selected_index() else {
+        return 0;
+    };
+    let mut start = state.table_offset.min(selected);
+    while start < selected && heights[start..=selected].iter().sum::<u16>() > rows_height {
+        start += 1;
+    }
+    while start > 0 && heights[start - 1..].iter().sum::<u16>() <= rows_height {
+        start -= 1;
+    }
+    start
+}
+
+// The todo row (and whether its status cell) at a screen position
+pub fn table_hit(state: &AppState, table: Rect, column: u16, row: u16) -> Option<TableHit> {
+    let inner = Rect::new(table.x + 1, table.y + TABLE_TOP, table.width.saturating_sub(2), rows_height(table));
+    if !inner.intersects(Rect::new(column, row, 1, 1)) {
+        return None;
+    }
+    let mut top = inner.y;
+    for (index, todo) in state.todos.iter().enumerate().skip(state.table_offset) {
+        let height = row_height(&todo.description);
+        if row < top + height {
+            let status = status_cell(inner);
+            let on_status = column >= status.x && column < status.x + status.width;
+            return Some(if on_status { TableHit::Status(index) } else { TableHit::Row(index) });
+        }
+        top += height;
+    }
+    None
+}
+
+// Mirrors how ratatui places the columns after the highlight symbol
+fn status_cell(inner: Rect) -> Rect {
+    let selection_width = HIGHLIGHT_SYMBOL.chars().count() as u16;
+    let [_, columns] = Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)]).areas(
+        Rect::new(inner.x, inner.y, inner.width, 1)
+    );
+    Layout::horizontal(TODO_COLUMNS).flex(Flex::Start).spacing(1).split(columns)[STATUS_COLUMN]
+}
+
+pub fn ui(f: &mut Frame, state: &AppState) {
+    let areas = areas(f.size(), state);
+    if let (Some(banner), Some(area)) = (&state.banner, areas.banner) {
+        render_banner(f, banner, area);
+    }
+
+    let header_cells = ["ID", "Description", "Status", "Phase", "Timer"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().fg(Color::Red)));
     let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-    let rows = todos.iter().map(|item| {
-        let height = item.description
-            .chars()
-            .filter(|c| *c == '\n')
-            .count()
-            .max(1) as u16;
+    let rows = state.todos.iter().map(|item| {
+        let height = row_height(&item.description);
         let status_style = match item.status {
             TodoStatus::Pending => Style::default().fg(Color::Yellow),
             TodoStatus::InProgress => Style::default().fg(Color::Cyan),
@@ -39,29 +180,234 @@ pub fn ui(f: &mut Frame, todos: &[Todo], input_buffer: &str, output_buffer: &str
             Cell::from(item.id.to_string()),
             Cell::from(item.description.clone()),
             Cell::from(item.status.to_string()).style(status_style),
-            Cell::from(item.timer.as_ref().map_or("--:--".to_string(), |t| t.output()))
+            Cell::from(item.timer.as_ref().map_or(String::new(), |t| t.phase_label(&state.cycle))),
+            Cell::from(
+                item.timer.as_ref().map_or("--:--".to_string(), |t| {
+                    if t.is_paused() { format!("⏸ {}", t.output()) } else { t.output() }
+                })
+            )
         ];
         Row::new(cells).height(height)
     });
 
-    let table = Table::new(rows, [
-        Constraint::Percentage(10),
-        Constraint::Percentage(50),
-        Constraint::Percentage(20),
-        Constraint::Percentage(20),
-    ])
+    let table = Table::new(rows, TODO_COLUMNS)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Todos"));
+        .block(Block::default().borders(Borders::ALL).title(todos_title(state)))
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(match state.focus {
+            Focus::Table => Style::default().add_modifier(Modifier::REVERSED),
+            Focus::Input => Style::default().add_modifier(Modifier::BOLD),
+        });
+    let mut table_state = TableState::default()
+        .with_offset(state.table_offset)
+        .with_selected(state.selected_index());
 
-    let output = Paragraph::new(output_buffer)
+    let log_lines: Vec<Line> = state.log
+        .lines()
+        .into_iter()
+        .map(|(time, text)| {
+            let time = time.map_or_else(|| " ".repeat(TIME_WIDTH), |time| format!("{} ", time));
+            Line::from(vec![Span::styled(time, Style::default().fg(Color::DarkGray)), Span::raw(text)])
+        })
+        .collect();
+    let log_len = log_lines.len();
+    let log_height = log_height(areas.output);
+    let log_top = log_len.saturating_sub(log_height + state.log.scroll);
+    let output_title = match state.log.scroll {
+        0 => "Output".to_string(),
+        lines => format!("Output ({} lines back · PgDn for newer)", lines),
+    };
+    let output = Paragraph::new(log_lines)
+        .scroll((log_top as u16, 0))
         .style(Style::default().fg(Color::Yellow))
-        .block(Block::default().borders(Borders::ALL).title("Output"));
+        .block(Block::default().borders(Borders::ALL).title(output_title));
+
+    // Scrolled sideways so the cursor stays inside the box
+    let before_cursor: String = state.input_buffer.chars().take(state.input_cursor).collect();
+    let cursor_x = Line::from(before_cursor).width() as u16;
+    let input_width = areas.input.width.saturating_sub(2);
+    let scroll = (cursor_x + 1).saturating_sub(input_width);
+    let input = Paragraph::new(state.input_buffer.as_str())
+        .scroll((0, scroll))
+        .style(Style::default().fg(Color::LightBlue))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(match state.focus {
+                    Focus::Input => "Input",
+                    Focus::Table => "Input (tab to type)",
+                })
+        );
 
-    let input = Paragraph::new(input_buffer)
+    match state.view {
+        View::Todos => {
+            f.render_stateful_widget(table, areas.table, &mut table_state);
+            let rows: u16 = state.todos
+                .iter()
+                .map(|t| row_height(&t.description))
+                .sum();
+            // Follows the cursor so it reaches the ends with the first and last rows
+            if rows > rows_height(areas.table) {
+                let track = Rect { y: areas.table.y + TABLE_TOP, height: rows_height(areas.table), ..areas.table };
+                render_scrollbar(f, track, state.todos.len(), state.selected_index().unwrap_or_default());
+            }
+        }
+        View::Stats => render_stats(f, state, areas.table),
+    }
+    f.render_widget(output, areas.output);
+    if log_len > log_height {
+        let track = areas.output.inner(&Margin { vertical: 1, horizontal: 0 });
+        render_scrollbar(f, track, log_len - log_height + 1, log_top);
+    }
+    f.render_widget(input, areas.input);
+    if state.focus == Focus::Input {
+        f.set_cursor(areas.input.x + 1 + cursor_x - scroll, areas.input.y + 1);
+    }
+    if let Some(completion) = &state.completion {
+        // Lined up with the start of the word being completed
+        let before_word: String = state.input_buffer.chars().take(completion.start).collect();
+        let word_x = (Line::from(before_word).width() as u16).saturating_sub(scroll);
+        render_completion(f, completion, areas.input, areas.input.x + word_x);
+    }
+}
+
+// A scrollbar on the right border, along `track`
+fn render_scrollbar(f: &mut Frame, track: Rect, positions: usize, position: usize) {
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None);
+    let mut scrollbar_state = ScrollbarState::new(positions).position(position);
+    f.render_stateful_widget(scrollbar, track, &mut scrollbar_state);
+}
+
+fn render_completion(f: &mut Frame, completion: &Completion, input: Rect, x: u16) {
+    let text_width = completion.candidates
+        .iter()
+        .map(|c| Line::from(c.text.as_str()).width())
+        .max()
+        .unwrap_or_default();
+    let items: Vec<ListItem> = completion.candidates
+        .iter()
+        .map(|c| {
+            ListItem::new(
+                Line::from(
+                    vec![
+                        Span::raw(format!("{:width$}  ", c.text, width = text_width)),
+                        Span::styled(c.detail.as_str(), Style::default().fg(Color::DarkGray))
+                    ]
+                )
+            )
+        })
+        .collect();
+    let line_width = completion.candidates
+        .iter()
+        .map(|c| text_width + 2 + Line::from(c.detail.as_str()).width())
+        .max()
+        .unwrap_or_default() as u16;
+
+    let screen = f.size();
+    let width = (line_width + 2).min(COMPLETION_WIDTH).min(screen.width);
+    let height = (completion.candidates.len() as u16).min(COMPLETION_ROWS) + 2;
+    let area = Rect {
+        x: x.min(screen.width - width),
+        y: input.y.saturating_sub(height),
+        width,
+        height: height.min(input.y),
+    };
+    let list = List::new(items)
         .style(Style::default().fg(Color::LightBlue))
-        .block(Block::default().borders(Borders::ALL).title("Input"));
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::default().borders(Borders::ALL).title("Tab: next · Esc: close"));
+    let mut list_state = ListState::default().with_selected(completion.selected);
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn todos_title(state: &AppState) -> String {
+    let title = match state.save_blocked {
+        Some(_) => format!("Todos — {} (read-only: not saving)", state.lists.current),
+        None => format!("Todos — {}", state.lists.current),
+    };
+    match state.focus {
+        Focus::Table => format!("{} · j/k move · space toggle · s start · d done · x delete · e edit · tab input", title),
+        Focus::Input => title,
+    }
+}
+
+fn render_banner(f: &mut Frame, banner: &Banner, area: Rect) {
+    let style = if banner.flash_on() {
+        Style::default().fg(Color::Black).bg(Color::Magenta).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+    };
+    let paragraph = Paragraph::new(banner.text.as_str())
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).title("⏰ Time's up (press any key)"));
+    f.render_widget(paragraph, area);
+}
+
+fn render_stats(f: &mut Frame, state: &AppState, area: Rect) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let stats = Stats::from_history(&state.history, now);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Percentage(40), Constraint::Min(0)].as_ref())
+        .split(area);
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(rows[1]);
+
+    let summary = Paragraph::new(stats.summary())
+        .style(Style::default().fg(Color::Green))
+        .block(Block::default().borders(Borders::ALL).title("Stats (type `stats` to go back)"));
+
+    let daily_title = format!(
+        "Pomodoros per day ({} – {})",
+        Stats::day_label(now, DAYS_SHOWN as u64 - 1),
+        Stats::day_label(now, 0)
+    );
+    let daily = Sparkline::default()
+        .data(&stats.per_day)
+        .style(Style::default().fg(Color::Red))
+        .block(Block::default().borders(Borders::ALL).title(daily_title));
+
+    let week_labels: Vec<String> = (0..stats.per_week.len())
+        .rev()
+        .map(|offset| if offset == 0 { "now".to_string() } else { format!("-{}w", offset) })
+        .collect();
+    let week_data: Vec<(&str, u64)> = week_labels
+        .iter()
+        .map(String::as_str)
+        .zip(stats.per_week.iter().copied())
+        .collect();
+    let weekly = BarChart::default()
+        .data(&week_data)
+        .bar_width(4)
+        .bar_style(Style::default().fg(Color::Red))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Red))
+        .block(Block::default().borders(Borders::ALL).title("Pomodoros per week"));
+
+    let todo_labels: Vec<String> = stats.per_todo
+        .iter()
+        .map(|(id, _, _)| format!("#{}", id))
+        .collect();
+    let todo_data: Vec<(&str, u64)> = todo_labels
+        .iter()
+        .map(String::as_str)
+        .zip(stats.per_todo.iter().map(|(_, _, secs)| secs / 60))
+        .collect();
+    let per_todo = BarChart::default()
+        .data(&todo_data)
+        .bar_width(5)
+        .bar_style(Style::default().fg(Color::Cyan))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Cyan))
+        .block(Block::default().borders(Borders::ALL).title("Focused minutes per todo"));
 
-    f.render_widget(table, chunks[0]);
-    f.render_widget(output, chunks[1]);
-    f.render_widget(input, chunks[2]);
+    f.render_widget(summary, rows[0]);
+    f.render_widget(daily, charts[0]);
+    f.render_widget(weekly, charts[1]);
+    f.render_widget(per_todo, rows[2]);
 }
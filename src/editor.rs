@@ -0,0 +1,184 @@
+use std::fs::{ self, OpenOptions };
+use std::io::{ self, Write };
+use std::path::Path;
+
+use crate::storage;
+
+// Commands typed in the TUI, one per line, kept in the data directory
+pub const COMMAND_HISTORY_FILE: &str = "command_history";
+pub const COMMAND_HISTORY_LIMIT: usize = 500;
+
+// Changes to the input line. The cursor is counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    Insert(char),
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+    // Ctrl-W: the word before the cursor
+    DeleteWordBack,
+    // Ctrl-U: everything before the cursor
+    DeleteToStart,
+    // Ctrl-K: everything after the cursor
+    DeleteToEnd,
+}
+
+// Applies `edit` to `text` with the cursor at `cursor` and returns where the cursor ends up
+pub fn apply(edit: Edit, text: &mut String, cursor: usize) -> usize {
+    let mut chars: Vec<char> = text.chars().collect();
+    let cursor = cursor.min(chars.len());
+    let cursor = match edit {
+        Edit::Insert(c) => {
+            chars.insert(cursor, c);
+            cursor + 1
+        }
+        Edit::Backspace if cursor > 0 => {
+            chars.remove(cursor - 1);
+            cursor - 1
+        }
+        Edit::Delete if cursor < chars.len() => {
+            chars.remove(cursor);
+            cursor
+        }
+        Edit::Backspace | Edit::Delete => cursor,
+        Edit::Left => cursor.saturating_sub(1),
+        Edit::Right => (cursor + 1).min(chars.len()),
+        Edit::WordLeft => word_start(&chars, cursor),
+        Edit::WordRight => word_end(&chars, cursor),
+        Edit::Home => 0,
+        Edit::End => chars.len(),
+        Edit::DeleteWordBack => {
+            let start = word_start(&chars, cursor);
+            chars.drain(start..cursor);
+            start
+        }
+        Edit::DeleteToStart => {
+            chars.drain(..cursor);
+            0
+        }
+        Edit::DeleteToEnd => {
+            chars.truncate(cursor);
+            cursor
+        }
+    };
+    *text = chars.into_iter().collect();
+    cursor
+}
+
+// Start of the word before `cursor`, skipping any spaces in between
+fn word_start(chars: &[char], cursor: usize) -> usize {
+    let mut i = cursor;
+    while i > 0 && chars[i - 1].is_whitespace() {
+        i -= 1;
+    }
+    while i > 0 && !chars[i - 1].is_whitespace() {
+        i -= 1;
+    }
+    i
+}
+
+// End of the word after `cursor`, skipping any spaces in between
+fn word_end(chars: &[char], cursor: usize) -> usize {
+    let mut i = cursor;
+    while i < chars.len() && chars[i].is_whitespace() {
+        i += 1;
+    }
+    while i < chars.len() && !chars[i].is_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+// Commands entered in earlier sessions and this one, browsed with Up and Down
+#[derive(Debug, Default)]
+pub struct CommandHistory {
+    entries: Vec<String>,
+    // The entry shown in the input while browsing
+    position: Option<usize>,
+    // What was typed before browsing started, given back past the newest entry
+    draft: String,
+}
+
+impl CommandHistory {
+    pub fn new(entries: Vec<String>) -> Self {
+        Self { entries, ..Self::default() }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    // Keeps the newest `COMMAND_HISTORY_LIMIT` commands, rewriting the file
+    // once it has grown past that. A missing or unreadable file is an empty history.
+    pub fn load(path: &Path) -> Self {
+        let data = fs::read_to_string(path).unwrap_or_default();
+        let mut entries: Vec<String> = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect();
+        if entries.len() > COMMAND_HISTORY_LIMIT {
+            entries.drain(..entries.len() - COMMAND_HISTORY_LIMIT);
+            let _ = storage::write_atomic(path, format!("{}\n", entries.join("\n")).as_bytes());
+        }
+        Self::new(entries)
+    }
+
+    pub fn append(path: &Path, command: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", command)
+    }
+
+    // Adds a command unless it repeats the last one, and stops browsing.
+    // Returns whether it was added.
+    pub fn push(&mut self, command: &str) -> bool {
+        self.position = None;
+        self.draft.clear();
+        let command = command.trim();
+        // A newline would split it into two entries in the file
+        if command.is_empty() || command.contains('\n') || self.entries.last().is_some_and(|last| last == command) {
+            return false;
+        }
+        self.entries.push(command.to_string());
+        if self.entries.len() > COMMAND_HISTORY_LIMIT {
+            self.entries.remove(0);
+        }
+        true
+    }
+
+    // The entry before the one shown, remembering `current` when browsing starts
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => {
+                return None;
+            }
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+
+    // The entry after the one shown, or the draft once past the newest
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            Some(&self.entries[position + 1])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::env;
+use std::fs;
+use std::io::{ self, Read, Write };
+use std::os::unix::net::{ UnixListener, UnixStream };
+use std::path::{ Path, PathBuf };
+use std::time::{ Duration, Instant };
+
+use crate::cli::Command;
+use crate::output::Report;
+use crate::models::todo::Todo;
+use crate::reduce::{ parse_command_for, Action };
+use crate::utils::OneOrMany;
+
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+// The TUI answers on its next loop iteration, so this only trips if it is stuck
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+// One socket per todo file, so a CLI call reaches the TUI that has that list open.
+// Lives in $XDG_RUNTIME_DIR, or the temp dir when that isn't set.
+pub fn socket_path(file: &Path) -> PathBuf {
+    let file = fs::canonicalize(file).unwrap_or_else(|_| {
+        env::current_dir().map(|cwd| cwd.join(file)).unwrap_or_else(|_| file.to_path_buf())
+    });
+    let dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    dir.join(format!("pomonote-{:016x}.sock", fnv1a(file.as_os_str().as_encoded_bytes())))
+}
+
+// Stable across builds, unlike DefaultHasher, so old and new binaries agree on the path
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| (hash ^ (*b as u64)).wrapping_mul(0x100000001b3))
+}
+
+// A request is either a JSON-encoded CLI command, e.g. {"command":"start","ids":[3]},
+// or a line in the TUI's own command syntax such as "start 3" or "done completed".
+// Only what the CLI commands can do is accepted, so a client can't quit the
+// TUI, switch its list or undo what was typed there.
+pub fn parse_request(line: &str, todos: &[Todo]) -> Result<Vec<Action>, String> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        return serde_json::from_str::<Command>(line)
+            .map(|command| command.actions())
+            .map_err(|e| format!("❌ Invalid request: {}", e));
+    }
+    let actions = match parse_command_for(line, todos) {
+        OneOrMany::One(action) => vec![action],
+        OneOrMany::Many(actions) => actions,
+    };
+    match actions.iter().find(|action| !is_command(action)) {
+        None => Ok(actions),
+        Some(Action::SetOutput(message)) => Err(message.clone()),
+        Some(_) => {
+            let name = line.split_whitespace().next().unwrap_or_default();
+            Err(format!("❌ '{}' can't be sent to a running pomonote.", name))
+        }
+    }
+}
+
+// Whether some `Command` stands for this action
+fn is_command(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::AddTodo(_) |
+            Action::EditTodo(..) |
+            Action::CompleteTodo(_) |
+            Action::StartTodo(_) |
+            Action::StartTodoFor(..) |
+            Action::StopTodo(_) |
+            Action::PauseTodo(_) |
+            Action::ResumeTodo(_) |
+            Action::RemoveTodo(_) |
+            Action::ToggleStatus(_) |
+            Action::ShowTrash |
+            Action::RestoreTodo(_) |
+            Action::EmptyTrash
+    )
+}
+
+pub struct Server {
+    listener: UnixListener,
+    path: PathBuf,
+    // Clients still sending their request line
+    pending: Vec<Pending>,
+}
+
+struct Pending {
+    stream: UnixStream,
+    received: Vec<u8>,
+    accepted: Instant,
+}
+
+pub struct Connection {
+    stream: UnixStream,
+    pub request: String,
+}
+
+impl Server {
+    // Fails with AddrInUse when another instance is already serving `file`.
+    // A socket left behind by a crashed instance is replaced.
+    pub fn bind(file: &Path) -> io::Result<Self> {
+        let path = socket_path(file);
+        let listener = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) if e.kind() == io::ErrorKind::AddrInUse => {
+                if UnixStream::connect(&path).is_ok() {
+                    return Err(e);
+                }
+                fs::remove_file(&path)?;
+                UnixListener::bind(&path)?
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        };
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, path, pending: Vec::new() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Accepts waiting clients and returns those whose request line has fully
+    // arrived, without blocking the event loop. A line can take several calls
+    // to come in; clients that don't finish it in time are dropped.
+    pub fn poll(&mut self) -> Vec<Connection> {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                self.pending.push(Pending { stream, received: Vec::new(), accepted: Instant::now() });
+            }
+        }
+        let mut connections = Vec::new();
+        let mut waiting = Vec::new();
+        for mut pending in std::mem::take(&mut self.pending) {
+            match pending.read() {
+                Ok(true) => connections.extend(pending.into_connection()),
+                Ok(false) if pending.accepted.elapsed() < READ_TIMEOUT => waiting.push(pending),
+                _ => {}
+            }
+        }
+        self.pending = waiting;
+        connections
+    }
+}
+
+impl Pending {
+    // Reads what has arrived so far. True once the line is complete; an error
+    // when the client hung up before finishing it.
+    fn read(&mut self) -> io::Result<bool> {
+        let mut chunk = [0; 1024];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                Ok(n) => {
+                    self.received.extend_from_slice(&chunk[..n]);
+                    if self.received.contains(&b'\n') {
+                        return Ok(true);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    return Ok(false);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn into_connection(self) -> Option<Connection> {
+        let end = self.received.iter().position(|&b| b == b'\n')?;
+        let request = String::from_utf8(self.received[..end].to_vec()).ok()?;
+        // The reply is one short line, written in one go
+        self.stream.set_nonblocking(false).ok()?;
+        self.stream.set_write_timeout(Some(READ_TIMEOUT)).ok()?;
+        Some(Connection { stream: self.stream, request })
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl Connection {
+    pub fn reply(mut self, report: &Report) {
+        if let Ok(json) = serde_json::to_string(report) {
+            let _ = writeln!(self.stream, "{}", json);
+        }
+    }
+}
+
+// Sends one request to the instance serving `file` and returns its JSON reply,
+// or None when nothing is listening
+pub fn send(file: &Path, request: &str) -> io::Result<Option<String>> {
+    let mut stream = match UnixStream::connect(socket_path(file)) {
+        Ok(stream) => stream,
+        Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused) => {
+            return Ok(None);
+        }
+        Err(e) => {
+            return Err(e);
+        }
+    };
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+    writeln!(stream, "{}", request)?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    Ok(Some(reply.trim_end().to_string()))
+}
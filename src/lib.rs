@@ -1,6 +1,21 @@
+pub mod cli;
+pub mod completion;
+pub mod config;
 pub mod display;
+pub mod editor;
+pub mod ipc;
+pub mod lists;
+pub mod log;
 pub mod models;
+pub mod notify;
+pub mod output;
 pub mod reduce;
+pub mod selector;
+pub mod stats;
+pub mod status;
+pub mod storage;
+pub mod tokenizer;
+pub mod undo;
 pub mod utils;
 pub mod app;
 pub mod commands;
@@ -0,0 +1,174 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::models::history::HistoryEntry;
+use crate::models::todo::{ Todo, TODOS_FILE };
+use crate::reduce::AppState;
+use crate::storage::{ self, PROJECT_FILE };
+
+pub const GLOBAL: &str = "global";
+pub const PROJECT: &str = "project";
+const LISTS_DIR: &str = "lists";
+
+// Known todo lists: the global one, named ones under <data dir>/lists, and the
+// `.pomonote.json` discovered for the current project
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lists {
+    pub data_dir: PathBuf,
+    pub project: Option<PathBuf>,
+    pub current: String,
+}
+
+impl Default for Lists {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::from("."),
+            project: None,
+            current: GLOBAL.to_string(),
+        }
+    }
+}
+
+impl Lists {
+    pub fn discover(cwd: &Path) -> Self {
+        Self {
+            data_dir: storage::data_dir().unwrap_or_else(|| PathBuf::from(".")),
+            project: storage::discover_project(cwd),
+            current: GLOBAL.to_string(),
+        }
+    }
+
+    // None for the project list when there is none, and for names that could
+    // point outside the lists directory
+    pub fn path(&self, name: &str) -> Option<PathBuf> {
+        match name {
+            GLOBAL => Some(self.data_dir.join(TODOS_FILE)),
+            PROJECT => self.project.clone(),
+            _ if is_valid_name(name) => Some(self.data_dir.join(LISTS_DIR).join(format!("{}.json", name))),
+            _ => None,
+        }
+    }
+
+    // The name shown for `file`, which may not be one of the known lists
+    pub fn name_for(&self, file: &Path) -> String {
+        if self.project.as_deref() == Some(file) {
+            return PROJECT.to_string();
+        }
+        if file == self.data_dir.join(TODOS_FILE) {
+            return GLOBAL.to_string();
+        }
+        file.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.display().to_string())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut named: Vec<String> = fs
+            ::read_dir(self.data_dir.join(LISTS_DIR))
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                    .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        named.sort();
+
+        let mut names = vec![GLOBAL.to_string()];
+        if self.project.is_some() {
+            names.push(PROJECT.to_string());
+        }
+        names.extend(named);
+        names
+    }
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() &&
+        name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') &&
+        name != PROJECT
+}
+
+// Saves the current list, then loads `name` (creating it first when `create` is set)
+pub fn switch(state: &mut AppState, name: &str, create: bool) {
+    if name != PROJECT && !is_valid_name(name) {
+        state.fail(format!("❌ Invalid list name '{}'. Use letters, digits, - and _.", name));
+        return;
+    }
+    let Some(path) = state.lists.path(name) else {
+        state.fail("❌ No .pomonote.json found for this project. Use `list init`.".to_string());
+        return;
+    };
+    if create && path.exists() {
+        state.fail(format!("❌ List '{}' already exists.", name));
+        return;
+    }
+    if !create && !path.exists() && name != GLOBAL {
+        state.fail(format!("❌ List '{}' not found. Use `list new {}`.", name, name));
+        return;
+    }
+    if state.save_blocked.is_none() && let Err(e) = Todo::save_file(&state.file, &state.todos, &state.trash) {
+        state.fail(format!("❌ Failed to save current list: {}", e));
+        return;
+    }
+    if create && let Err(e) = Todo::save_all(&path, &[]) {
+        state.fail(format!("❌ Failed to create list '{}': {}", name, e));
+        return;
+    }
+    let (stored, warning) = match storage::load_or_quarantine(&path) {
+        Ok(loaded) => loaded,
+        Err(message) => {
+            state.output_buffer = message;
+            return;
+        }
+    };
+
+    state.todos = stored.todos.clone();
+    state.trash = stored.trash.clone();
+    state.saved = stored;
+    state.undo.clear();
+    state.dirty = false;
+    state.save_blocked = None;
+    state.history = HistoryEntry::load_all(&HistoryEntry::path_for(&path));
+    state.disk_stamp = storage::stamp(&path);
+    state.file = path;
+    state.lists.current = name.to_string();
+    state.output_buffer = match warning {
+        Some(warning) => warning,
+        None if create => format!("📋 Created and switched to list '{}'.", name),
+        None => format!("📋 Switched to list '{}'.", name),
+    };
+}
+
+// Creates `.pomonote.json` at the project root and switches to it
+pub fn init_project(state: &mut AppState, cwd: &Path) {
+    if let Some(project) = &state.lists.project {
+        state.fail(format!("❌ This project already has a list at {}.", project.display()));
+        return;
+    }
+    let path = storage::project_root(cwd).join(PROJECT_FILE);
+    if let Err(e) = Todo::save_all(&path, &[]) {
+        state.fail(format!("❌ Failed to create {}: {}", path.display(), e));
+        return;
+    }
+    state.lists.project = Some(path.clone());
+    switch(state, PROJECT, false);
+    state.output_buffer = format!("📋 Created project list at {}.", path.display());
+}
+
+pub fn show(state: &mut AppState) {
+    let names = state.lists.names();
+    let lines: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let marker = if *name == state.lists.current { "▶" } else { " " };
+            match (name.as_str(), &state.lists.project) {
+                (PROJECT, Some(path)) => format!("{} {} ({})", marker, name, path.display()),
+                _ => format!("{} {}", marker, name),
+            }
+        })
+        .collect();
+    state.output_buffer = format!("📋 Lists:\n{}", lines.join("\n"));
+}
@@ -0,0 +1,68 @@
+use crate::utils::clock;
+
+pub const OUTPUT_LOG_LIMIT: usize = 200;
+// "HH:MM:SS " in front of each message
+pub const TIME_WIDTH: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub at: u64,
+    pub text: String,
+}
+
+// Messages shown in the Output pane this session, oldest first
+#[derive(Debug, Default)]
+pub struct OutputLog {
+    entries: Vec<LogEntry>,
+    // Lines scrolled back from the newest; 0 follows new messages
+    pub scroll: usize,
+}
+
+impl OutputLog {
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    // Adds a message and scrolls back down so it is seen. Blank ones are dropped.
+    pub fn push(&mut self, at: u64, text: &str) {
+        let text = text.trim_end();
+        if text.trim().is_empty() {
+            return;
+        }
+        self.entries.push(LogEntry { at, text: text.to_string() });
+        if self.entries.len() > OUTPUT_LOG_LIMIT {
+            self.entries.remove(0);
+        }
+        self.scroll = 0;
+    }
+
+    // Each line as drawn: the time, then the message with later lines indented under it
+    pub fn lines(&self) -> Vec<(Option<String>, &str)> {
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                entry.text
+                    .lines()
+                    .enumerate()
+                    .map(move |(i, line)| ((i == 0).then(|| clock(entry.at)), line))
+            })
+            .collect()
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|e| e.text.lines().count())
+            .sum()
+    }
+
+    // Moves the view by `delta` lines, positive going back in time
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self.scroll.saturating_add_signed(delta);
+    }
+
+    // Keeps the view from going past the oldest line when `height` lines are shown
+    pub fn fit(&mut self, height: usize) {
+        self.scroll = self.scroll.min(self.line_count().saturating_sub(height));
+    }
+}
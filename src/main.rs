@@ -1,24 +1,41 @@
-mod app;
-mod display;
-mod models;
-mod commands;
-mod reduce;
-mod utils;
-
+use clap::Parser;
 use crossterm::{
     event::{ EnableMouseCapture },
     execute,
     terminal::{ enable_raw_mode, EnterAlternateScreen },
 };
-use models::todo::Todo;
+use pomonote::app;
+use pomonote::cli::{ self, Cli, Command };
+use pomonote::output::Format;
+use pomonote::storage;
+use pomonote::utils::{ setup_panic_handler, cleanup_terminal };
 use ratatui::{ backend::{ CrosstermBackend }, Terminal };
-use std::{ error::Error, io };
-use utils::{ setup_panic_handler, cleanup_terminal };
-fn main() -> Result<(), Box<dyn Error>> {
-    start_app()
+use std::{ error::Error, io, process::ExitCode };
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(command @ Command::Status { .. }) => Ok(cli::status(cli.file, &command, cli.format)),
+        Some(command) => Ok(cli::run(app::load_state(cli.file)?, command, cli.format)),
+        // There is no TUI to show in JSON mode, so print the list instead
+        None if cli.format == Format::Json => {
+            Ok(cli::run(app::load_state(cli.file)?, Command::Ls, cli.format))
+        }
+        None => {
+            start_app(cli)?;
+            Ok(ExitCode::SUCCESS)
+        }
+    }
 }
 
-fn start_app() -> Result<(), Box<dyn Error>> {
+fn start_app(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let mut state = app::load_state(cli.file)?;
+    if state.save_blocked.is_none() &&
+        let Err(e) = storage::rotate_backups(&state.file, storage::BACKUPS_KEPT)
+    {
+        state.output_buffer.push_str(&format!("\n⚠️  Could not back up {}: {}", state.file.display(), e));
+    }
+
     setup_panic_handler();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -26,8 +43,7 @@ fn start_app() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let todos = get_todos();
-    let res = app::run_app(&mut terminal, todos);
+    let res = app::run_app(&mut terminal, state);
     let cleanup_result = cleanup_terminal(terminal);
 
     if let Err(err) = res {
@@ -38,7 +54,3 @@ fn start_app() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
-
-fn get_todos() -> Vec<Todo> {
-    Todo::load_all()
-}
@@ -0,0 +1,59 @@
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Phase {
+    #[default]
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Work => write!(f, "Work"),
+            Phase::ShortBreak => write!(f, "Short Break"),
+            Phase::LongBreak => write!(f, "Long Break"),
+        }
+    }
+}
+
+// Phase lengths in seconds, plus how many work blocks make up a full set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub work: u64,
+    pub short_break: u64,
+    pub long_break: u64,
+    pub long_break_every: u32,
+}
+
+impl Cycle {
+    pub fn duration(&self, phase: Phase) -> u64 {
+        match phase {
+            Phase::Work => self.work,
+            Phase::ShortBreak => self.short_break,
+            Phase::LongBreak => self.long_break,
+        }
+    }
+
+    // Returns the phase and round that follow `phase` in `round`
+    pub fn next(&self, phase: Phase, round: u32) -> (Phase, u32) {
+        match phase {
+            Phase::Work if round >= self.long_break_every.max(1) => (Phase::LongBreak, round),
+            Phase::Work => (Phase::ShortBreak, round),
+            Phase::ShortBreak => (Phase::Work, round + 1),
+            Phase::LongBreak => (Phase::Work, 1),
+        }
+    }
+}
+
+impl Default for Cycle {
+    fn default() -> Self {
+        Self {
+            work: 1500,
+            short_break: 300,
+            long_break: 900,
+            long_break_every: 4,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{ Deserialize, Serialize };
+use std::fs::{ self, OpenOptions };
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use super::cycle::Phase;
+use super::timer::Timer;
+use super::todo::Todo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Finished,
+    Interrupted,
+}
+
+// One work block, stored as a line of JSON in the append-only history file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub todo_id: u32,
+    pub description: String,
+    pub start: u64,
+    pub end: u64,
+    // Seconds of focused time
+    pub duration: u64,
+    pub outcome: Outcome,
+}
+
+impl HistoryEntry {
+    pub fn finished(todo: &Todo) -> Option<Self> {
+        let timer = todo.timer.as_ref()?;
+        let start = Self::work_start(timer)?;
+        Some(Self {
+            todo_id: todo.id,
+            description: todo.description.clone(),
+            start,
+            end: start + timer.duration(),
+            duration: timer.duration(),
+            outcome: Outcome::Finished,
+        })
+    }
+
+    // Records the part of a work block that was cut short by stop, complete or remove
+    pub fn interrupted(todo: &Todo) -> Option<Self> {
+        let timer = todo.timer.as_ref()?;
+        let start = Self::work_start(timer)?;
+        let duration = timer.elapsed_seconds();
+        if duration == 0 {
+            return None;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        Some(Self {
+            todo_id: todo.id,
+            description: todo.description.clone(),
+            start,
+            end: now.max(start + duration),
+            duration,
+            outcome: Outcome::Interrupted,
+        })
+    }
+
+    fn work_start(timer: &Timer) -> Option<u64> {
+        match timer.phase() {
+            Phase::Work => timer.started_at(),
+            _ => None,
+        }
+    }
+
+    // `todos.json` keeps its history in `todos.history.jsonl` alongside it
+    pub fn path_for(todos_path: &Path) -> PathBuf {
+        let stem = todos_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "todos".to_string());
+        todos_path.with_file_name(format!("{}.history.jsonl", stem))
+    }
+
+    pub fn append(path: &Path, entries: &[HistoryEntry]) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for entry in entries {
+            let line = serde_json::to_string(entry)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    // Lines that fail to parse are skipped so one bad write can't hide the rest
+    pub fn load_all(path: &Path) -> Vec<HistoryEntry> {
+        fs::read_to_string(path)
+            .map(|data| {
+                data.lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn for_todo(entries: &[HistoryEntry], id: u32) -> Vec<&HistoryEntry> {
+        entries
+            .iter()
+            .filter(|e| e.todo_id == id)
+            .collect()
+    }
+
+    pub fn between(entries: &[HistoryEntry], from: u64, to: u64) -> Vec<&HistoryEntry> {
+        entries
+            .iter()
+            .filter(|e| e.start >= from && e.start < to)
+            .collect()
+    }
+}
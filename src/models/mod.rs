@@ -1,2 +1,4 @@
+pub mod cycle;
+pub mod history;
 pub mod timer;
 pub mod todo;
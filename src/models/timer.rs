@@ -1,23 +1,47 @@
-use std::time::{ Instant, SystemTime, UNIX_EPOCH };
+use std::time::{ Duration, Instant, SystemTime, UNIX_EPOCH };
 use serde::{ Deserialize, Serialize };
+use super::cycle::{ Cycle, Phase };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Timer {
     duration: u64,
     start_timestamp: Option<u64>,
+    #[serde(default)]
+    phase: Phase,
+    #[serde(default = "first_round")]
+    round: u32,
+    #[serde(default)]
+    paused_at: Option<u64>,
     #[serde(skip)]
     start_instant: Option<Instant>,
 }
 
+fn first_round() -> u32 {
+    1
+}
+
 impl Timer {
     pub fn new() -> Self {
+        Self::for_phase(Phase::Work, 1, &Cycle::default())
+    }
+
+    pub fn for_phase(phase: Phase, round: u32, cycle: &Cycle) -> Self {
         Self {
-            duration: 1500,
+            duration: cycle.duration(phase),
             start_timestamp: None,
+            phase,
+            round,
+            paused_at: None,
             start_instant: None,
         }
     }
 
+    // Overrides the phase length, e.g. for `start 3 50m`
+    pub fn with_duration(mut self, duration: u64) -> Self {
+        self.duration = duration;
+        self
+    }
+
     pub fn start(&mut self) {
         let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
 
@@ -25,7 +49,68 @@ impl Timer {
         self.start_instant = Some(Instant::now());
     }
 
+    // Freezes the countdown; elapsed time is kept relative to start_timestamp
+    pub fn pause(&mut self) -> bool {
+        if self.start_timestamp.is_none() || self.paused_at.is_some() {
+            return false;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        self.paused_at = Some(now);
+        true
+    }
+
+    // Shifts start_timestamp forward by the paused time so it survives a save/load round-trip
+    pub fn resume(&mut self) -> bool {
+        let (Some(start_ts), Some(paused_at)) = (self.start_timestamp, self.paused_at) else {
+            return false;
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        self.start_timestamp = Some(start_ts + now.saturating_sub(paused_at));
+        self.paused_at = None;
+        self.start_instant = None;
+        self.restore_instant();
+        true
+    }
+
+    pub fn started_at(&self) -> Option<u64> {
+        self.start_timestamp
+    }
+
+    pub fn elapsed_seconds(&self) -> u64 {
+        match self.start_timestamp {
+            Some(_) => self.duration - self.remaining_seconds(),
+            None => 0,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    // Moves on to the next phase of the cycle and starts it straight away
+    pub fn advance(&mut self, cycle: &Cycle) -> Phase {
+        let (phase, round) = cycle.next(self.phase, self.round);
+        *self = Self::for_phase(phase, round, cycle);
+        self.start();
+        phase
+    }
+
     pub fn remaining_seconds(&self) -> u64 {
+        if let (Some(start_ts), Some(paused_at)) = (self.start_timestamp, self.paused_at) {
+            return self.duration.saturating_sub(paused_at.saturating_sub(start_ts));
+        }
         let elapsed = match (self.start_timestamp, self.start_instant) {
             (Some(start_ts), Some(instant)) => {
                 let instant_elapsed = instant.elapsed().as_secs();
@@ -46,11 +131,7 @@ impl Timer {
                 return self.duration;
             }
         };
-        if elapsed >= self.duration {
-            0
-        } else {
-            self.duration - elapsed
-        }
+        self.duration.saturating_sub(elapsed)
     }
 
     pub fn is_finished(&self) -> bool {
@@ -64,13 +145,19 @@ impl Timer {
         format!("{:02}:{:02}", minutes, seconds)
     }
 
+    // e.g. "Work 2/4" or "Long Break"
+    pub fn phase_label(&self, cycle: &Cycle) -> String {
+        match self.phase {
+            Phase::Work => format!("{} {}/{}", self.phase, self.round, cycle.long_break_every),
+            _ => self.phase.to_string(),
+        }
+    }
+
     pub fn restore_instant(&mut self) {
-        if let Some(start_ts) = self.start_timestamp {
-            if self.start_instant.is_none() {
-                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                let elapsed = now.saturating_sub(start_ts);
-                self.start_instant = Some(Instant::now());
-            }
+        if let Some(start_ts) = self.start_timestamp && self.start_instant.is_none() {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            let elapsed = Duration::from_secs(now.saturating_sub(start_ts));
+            self.start_instant = Instant::now().checked_sub(elapsed);
         }
     }
 }
@@ -1,8 +1,14 @@
 use serde::{ Deserialize, Serialize };
 use super::timer::Timer;
+use crate::storage;
 use std::fs;
+use std::io;
 use std::path::Path;
 
+pub const TODOS_FILE: &str = "todos.json";
+// Days a removed todo stays in the trash by default
+pub const TRASH_DAYS: u64 = 30;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Todo {
     pub id: u32,
@@ -11,6 +17,59 @@ pub struct Todo {
     pub timer: Option<Timer>,
 }
 
+// A todo removed with `rm`, kept until it is restored, the trash is emptied
+// or it expires
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trashed {
+    pub todo: Todo,
+    // Unix seconds
+    pub deleted_at: u64,
+}
+
+impl Trashed {
+    // Whether it has been in the trash for `days` or more; 0 keeps it forever
+    pub fn is_expired(&self, now: u64, days: u64) -> bool {
+        days > 0 && now.saturating_sub(self.deleted_at) >= days.saturating_mul(86400)
+    }
+
+    // Combines the trash changed on disk with unsaved local changes, given the
+    // trash as last loaded or saved (`base`). Entries restored or emptied on
+    // either side stay gone; entries trashed on either side are kept.
+    pub fn merge(base: &[Trashed], disk: Vec<Trashed>, local: &[Trashed]) -> Vec<Trashed> {
+        let find = |trash: &[Trashed], entry: &Trashed| trash.iter().any(|t| t.is(entry));
+        let mut merged: Vec<Trashed> = disk
+            .into_iter()
+            .filter(|t| !find(base, t) || find(local, t))
+            .collect();
+        for trashed in local {
+            if !find(base, trashed) && !find(&merged, trashed) {
+                merged.push(trashed.clone());
+            }
+        }
+        merged
+    }
+
+    // The same removal of the same todo
+    fn is(&self, other: &Trashed) -> bool {
+        self.todo.id == other.todo.id && self.deleted_at == other.deleted_at
+    }
+}
+
+// Everything stored in a list file. Files written before the trash existed
+// are a bare array of todos and load with an empty trash.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TodoFile {
+    pub todos: Vec<Todo>,
+    #[serde(default)]
+    pub trash: Vec<Trashed>,
+}
+
+#[derive(Serialize)]
+struct TodoFileRef<'a> {
+    todos: &'a [Todo],
+    trash: &'a [Trashed],
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TodoStatus {
     Pending,
@@ -28,6 +87,26 @@ impl std::fmt::Display for TodoStatus {
     }
 }
 
+#[derive(Debug)]
+pub enum LoadError {
+    // The file exists but couldn't be read, e.g. permissions
+    Io(io::Error),
+    // The file was read but isn't a valid todo list
+    Parse(serde_json::Error),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "could not read file: {}", e),
+            LoadError::Parse(e) =>
+                write!(f, "invalid JSON at line {}, column {}: {}", e.line(), e.column(), e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
 impl Todo {
     pub fn new(id: u32, description: String) -> Self {
         Self {
@@ -38,39 +117,94 @@ impl Todo {
         }
     }
 
-    pub fn save_all(todos: &Vec<Todo>) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(todos)?;
-        fs::write("todos.json", json)?;
-        Ok(())
+    // Writes a list with an empty trash
+    pub fn save_all(path: &Path, todos: &[Todo]) -> std::io::Result<()> {
+        Todo::save_file(path, todos, &[])
+    }
+
+    pub fn save_file(path: &Path, todos: &[Todo], trash: &[Trashed]) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&TodoFileRef { todos, trash })?;
+        storage::write_atomic(path, json.as_bytes())
     }
 
-    pub fn load_all() -> Vec<Todo> {
-        let path = Path::new("todos.json");
+    pub fn load_all(path: &Path) -> Result<Vec<Todo>, LoadError> {
+        Todo::load_file(path).map(|file| file.todos)
+    }
 
+    // A missing file is an empty list; anything unreadable is an error so it never gets overwritten
+    pub fn load_file(path: &Path) -> Result<TodoFile, LoadError> {
         if !path.exists() {
-            return Vec::new();
+            return Ok(TodoFile::default());
         }
 
-        let mut todos: Vec<Todo> = fs
-            ::read_to_string(path)
-            .ok()
-            .and_then(|data| serde_json::from_str(&data).ok())
-            .unwrap_or_else(Vec::new);
+        let data = fs::read_to_string(path).map_err(LoadError::Io)?;
+        // Decided up front rather than with an untagged enum, which would lose the error position
+        let mut file = if data.trim_start().starts_with('[') {
+            TodoFile {
+                todos: serde_json::from_str(&data).map_err(LoadError::Parse)?,
+                trash: Vec::new(),
+            }
+        } else {
+            serde_json::from_str(&data).map_err(LoadError::Parse)?
+        };
 
-        for todo in &mut todos {
+        for todo in &mut file.todos {
             if let Some(timer) = &mut todo.timer {
                 timer.restore_instant();
             }
         }
 
-        todos
+        Ok(file)
     }
 
-    pub fn next_id(todos: &Vec<Todo>) -> u32 {
+    pub fn next_id(todos: &[Todo]) -> u32 {
         todos
             .iter()
             .map(|t| t.id)
             .max()
             .unwrap_or(0) + 1
     }
+
+    // Combines a list changed on disk with unsaved local changes, given the
+    // list as last loaded or saved (`base`). Todos changed locally keep the
+    // local copy, the rest take the disk copy. Todos removed on one side and
+    // left alone on the other stay removed. Todos added locally are kept,
+    // renumbered if their id was taken by a different todo on disk.
+    pub fn merge(base: &[Todo], disk: Vec<Todo>, local: &[Todo]) -> Vec<Todo> {
+        let find = |todos: &[Todo], id: u32| todos.iter().find(|t| t.id == id).cloned();
+        let on_disk: Vec<u32> = disk.iter().map(|t| t.id).collect();
+        let mut merged: Vec<Todo> = disk
+            .into_iter()
+            .filter_map(|todo| {
+                match (find(base, todo.id), find(local, todo.id)) {
+                    (Some(old), None) if old.same(&todo) => None,
+                    (Some(old), Some(mine)) if !old.same(&mine) => Some(mine),
+                    _ => Some(todo),
+                }
+            })
+            .collect();
+
+        for todo in local {
+            match find(base, todo.id) {
+                Some(_) if on_disk.contains(&todo.id) => {}
+                Some(old) if old.same(todo) => {}
+                _ => {
+                    match merged.iter().find(|t| t.id == todo.id) {
+                        Some(existing) if existing.description == todo.description => {}
+                        Some(_) => {
+                            let id = Todo::next_id(&merged);
+                            merged.push(Todo { id, ..todo.clone() });
+                        }
+                        None => merged.push(todo.clone()),
+                    }
+                }
+            }
+        }
+        merged
+    }
+
+    // Whether two copies of a todo would be stored the same way
+    fn same(&self, other: &Todo) -> bool {
+        serde_json::to_value(self).ok() == serde_json::to_value(other).ok()
+    }
 }
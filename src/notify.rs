@@ -0,0 +1,86 @@
+use serde::{ Deserialize, Serialize };
+use std::io::{ self, Write };
+use std::process::{ Command, Stdio };
+use std::thread;
+use std::time::{ Duration, Instant };
+
+const BANNER_TIMEOUT: Duration = Duration::from_secs(30);
+const BANNER_FLASH: Duration = Duration::from_millis(500);
+
+// The `notify` section of config.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    pub bell: bool,
+    pub banner: bool,
+    // Run through `sh -c`, e.g. "notify-send \"$POMONOTE_TITLE\" \"$POMONOTE_BODY\""
+    pub command: Option<String>,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            bell: true,
+            banner: true,
+            command: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub todo_id: u32,
+    pub title: String,
+    pub body: String,
+}
+
+// Flashing message shown above the todo table until dismissed or timed out
+#[derive(Debug, Clone)]
+pub struct Banner {
+    pub text: String,
+    shown_at: Instant,
+}
+
+impl Banner {
+    pub fn new(text: String) -> Self {
+        Self { text, shown_at: Instant::now() }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= BANNER_TIMEOUT
+    }
+
+    pub fn flash_on(&self) -> bool {
+        (self.shown_at.elapsed().as_millis() / BANNER_FLASH.as_millis()).is_multiple_of(2)
+    }
+}
+
+pub fn dispatch(config: &NotifyConfig, notification: &Notification) {
+    if config.bell {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+
+    if let Some(command) = &config.command {
+        run_hook(command, notification);
+    }
+}
+
+fn run_hook(command: &str, notification: &Notification) {
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("POMONOTE_TITLE", &notification.title)
+        .env("POMONOTE_BODY", &notification.body)
+        .env("POMONOTE_TODO_ID", notification.todo_id.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    // Reap the hook in the background so a slow command never blocks the UI
+    if let Ok(mut child) = child {
+        thread::spawn(move || child.wait());
+    }
+}
@@ -0,0 +1,173 @@
+use clap::ValueEnum;
+use serde::{ Deserialize, Serialize };
+
+use crate::models::cycle::Phase;
+use crate::models::timer::Timer;
+use crate::models::todo::{ Todo, TodoStatus };
+use crate::reduce::AppState;
+use crate::stats::Stats;
+
+// Bump when a field is removed or changes meaning; adding fields is fine
+pub const JSON_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+// The document printed for every command in `--format json` mode. It is kept
+// separate from the storage format so the file layout can change without
+// breaking scripts.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub version: u32,
+    pub list: String,
+    pub ok: bool,
+    pub warnings: Vec<String>,
+    pub results: Vec<CommandResult>,
+    pub todos: Vec<TodoJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<StatsJson>,
+}
+
+impl Report {
+    pub fn new(state: &AppState, warnings: Vec<String>, results: Vec<CommandResult>) -> Self {
+        Self {
+            version: JSON_VERSION,
+            list: state.lists.current.clone(),
+            ok: results.iter().all(|r| r.ok),
+            warnings,
+            results,
+            todos: state.todos.iter().map(TodoJson::from).collect(),
+            stats: None,
+        }
+    }
+}
+
+// The parts of a Report a client needs to print a reply in text mode
+#[derive(Debug, Deserialize)]
+pub struct Reply {
+    pub ok: bool,
+    pub results: Vec<CommandResult>,
+}
+
+// `pomonote status --format json`; `todo` is null when nothing is running
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub version: u32,
+    pub text: String,
+    pub todo: Option<TodoJson>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandResult {
+    pub ok: bool,
+    pub message: String,
+}
+
+impl CommandResult {
+    pub fn success(message: String) -> Self {
+        Self { ok: true, message }
+    }
+
+    pub fn error(message: String) -> Self {
+        Self { ok: false, message }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoJson {
+    pub id: u32,
+    pub description: String,
+    pub status: &'static str,
+    pub timer: Option<TimerJson>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimerJson {
+    pub phase: &'static str,
+    pub round: u32,
+    pub duration: u64,
+    pub remaining_seconds: u64,
+    pub started_at: Option<u64>,
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatsJson {
+    pub today: u64,
+    pub total: u64,
+    pub focused_seconds: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub per_day: Vec<u64>,
+    pub per_week: Vec<u64>,
+    pub per_todo: Vec<TodoTimeJson>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoTimeJson {
+    pub id: u32,
+    pub description: String,
+    pub seconds: u64,
+}
+
+pub fn status_name(status: &TodoStatus) -> &'static str {
+    match status {
+        TodoStatus::Pending => "pending",
+        TodoStatus::InProgress => "in_progress",
+        TodoStatus::Completed => "completed",
+    }
+}
+
+pub fn phase_name(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Work => "work",
+        Phase::ShortBreak => "short_break",
+        Phase::LongBreak => "long_break",
+    }
+}
+
+impl From<&Todo> for TodoJson {
+    fn from(todo: &Todo) -> Self {
+        Self {
+            id: todo.id,
+            description: todo.description.clone(),
+            status: status_name(&todo.status),
+            timer: todo.timer.as_ref().map(TimerJson::from),
+        }
+    }
+}
+
+impl From<&Timer> for TimerJson {
+    fn from(timer: &Timer) -> Self {
+        Self {
+            phase: phase_name(timer.phase()),
+            round: timer.round(),
+            duration: timer.duration(),
+            remaining_seconds: timer.remaining_seconds(),
+            started_at: timer.started_at(),
+            paused: timer.is_paused(),
+        }
+    }
+}
+
+impl From<Stats> for StatsJson {
+    fn from(stats: Stats) -> Self {
+        Self {
+            today: stats.today,
+            total: stats.total,
+            focused_seconds: stats.focused_seconds,
+            current_streak: stats.current_streak,
+            best_streak: stats.best_streak,
+            per_day: stats.per_day,
+            per_week: stats.per_week,
+            per_todo: stats.per_todo
+                .into_iter()
+                .map(|(id, description, seconds)| TodoTimeJson { id, description, seconds })
+                .collect(),
+        }
+    }
+}
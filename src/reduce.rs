@@ -1,125 +1,500 @@
-use crate::models::todo::{ self, Todo, TodoStatus };
+use crate::models::cycle::Cycle;
+use crate::models::history::HistoryEntry;
+use crate::models::todo::{ Todo, TodoFile, Trashed, TODOS_FILE, TRASH_DAYS };
 use crate::commands;
-use crate::utils::OneOrMany;
+use crate::completion::{ self, Completion };
+use crate::editor::{ self, CommandHistory, Edit };
+use crate::lists::{ self, Lists };
+use crate::log::OutputLog;
+use std::borrow::Borrow;
+use std::path::PathBuf;
+use crate::notify::{ Banner, Notification, NotifyConfig };
+use crate::storage::Stamp;
+use crate::selector::{ select, Selector, SelectorError };
+use crate::tokenizer::{ tokenize, Token };
+use crate::undo::UndoStack;
+use crate::utils::{ parse_duration, OneOrMany };
 
 // Centralized application state
 
 pub struct AppState {
     pub todos: Vec<Todo>,
+    // Removed todos, stored alongside `todos` until restored or expired
+    pub trash: Vec<Trashed>,
+    pub trash_days: u64,
+    // Where `todos` are loaded from and saved to
+    pub file: PathBuf,
     pub input_buffer: String,
+    // Cursor position in `input_buffer`, in characters
+    pub input_cursor: usize,
+    pub command_history: CommandHistory,
+    // Suggestions shown above the input after Tab, while there is more than one
+    pub completion: Option<Completion>,
+    // The latest message, moved into `log` by the event loop
     pub output_buffer: String,
+    pub log: OutputLog,
     pub should_quit: bool,
+    pub cycle: Cycle,
+    pub notify: NotifyConfig,
+    pub banner: Option<Banner>,
+    pub history: Vec<HistoryEntry>,
+    pub view: View,
+    pub focus: Focus,
+    // Cursor row in the todo table. It can point past the end after a removal,
+    // so read it through `selected_index`.
+    pub selected: usize,
+    // First row shown in the todo table, see `display::fit_offset`
+    pub table_offset: usize,
+    pub lists: Lists,
+    // Set when `todos` changed and haven't been written to `file` yet
+    pub dirty: bool,
+    // Why `file` must not be written, e.g. it couldn't be read and is still in place
+    pub save_blocked: Option<String>,
+    // Set by commands that report an error, so callers outside the TUI can tell
+    pub failed: bool,
+    // What `file` looked like when last loaded or saved, to notice other writers
+    pub disk_stamp: Option<Stamp>,
+    // The todos and trash as last loaded or saved, to tell local changes from
+    // other writers' when merging
+    pub saved: TodoFile,
+    // Snapshots of `todos` before and after each command, for undo and redo
+    pub undo: UndoStack,
+    // Side effects queued by the reducer and carried out by the event loop
+    pub effects: Vec<Effect>,
+}
+
+impl AppState {
+    pub fn new(todos: Vec<Todo>) -> Self {
+        Self {
+            todos,
+            trash: Vec::new(),
+            trash_days: TRASH_DAYS,
+            file: PathBuf::from(TODOS_FILE),
+            input_buffer: String::new(),
+            input_cursor: 0,
+            command_history: CommandHistory::default(),
+            completion: None,
+            output_buffer: String::new(),
+            log: OutputLog::default(),
+            should_quit: false,
+            cycle: Cycle::default(),
+            notify: NotifyConfig::default(),
+            banner: None,
+            history: Vec::new(),
+            view: View::Todos,
+            focus: Focus::Input,
+            selected: 0,
+            table_offset: 0,
+            lists: Lists::default(),
+            dirty: false,
+            save_blocked: None,
+            failed: false,
+            disk_stamp: None,
+            saved: TodoFile::default(),
+            undo: UndoStack::default(),
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn fail(&mut self, message: String) {
+        self.output_buffer = message;
+        self.failed = true;
+    }
+
+    // Logs the unfinished part of a running work block before its timer is discarded
+    pub fn record_interruption(&mut self, id: u32) {
+        let entry = self.todos
+            .iter()
+            .find(|t| t.id == id)
+            .and_then(HistoryEntry::interrupted);
+        if let Some(entry) = entry {
+            self.record(entry);
+        }
+    }
+
+    // Replaces the input and puts the cursor at its end
+    pub fn set_input(&mut self, text: String) {
+        self.input_cursor = text.chars().count();
+        self.input_buffer = text;
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        (!self.todos.is_empty()).then(|| self.selected.min(self.todos.len() - 1))
+    }
+
+    pub fn selected_todo(&self) -> Option<&Todo> {
+        self.selected_index().map(|i| &self.todos[i])
+    }
+
+    // Drops todos that have been in the trash longer than `trash_days`
+    pub fn expire_trash(&mut self, now: u64) {
+        let days = self.trash_days;
+        let before = self.trash.len();
+        self.trash.retain(|t| !t.is_expired(now, days));
+        if self.trash.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    // Keeps the in-memory history used by the stats view in step with the log file
+    pub fn record(&mut self, entry: HistoryEntry) {
+        self.history.push(entry.clone());
+        self.effects.push(Effect::Record(entry));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Todos,
+    Stats,
+}
+
+// Where typed keys go: the command input, or single-key actions on the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Input,
+    Table,
+}
+
+pub enum Effect {
+    Notify(Notification),
+    Record(HistoryEntry),
+    ShowLists,
+    SwitchList {
+        name: String,
+        create: bool,
+    },
+    InitProjectList,
+    // Adds a command typed in the TUI to the history file
+    SaveCommand(String),
 }
 
 // All possible actions
 pub enum Action {
     Quit,
     AddTodo(String),
+    EditTodo(u32, String),
+    // Fills the input with the todo's text for editing
+    BeginEdit(u32),
     RemoveTodo(u32),
     CompleteTodo(u32),
     StartTodo(u32),
+    StartTodoFor(u32, u64),
     StopTodo(u32),
+    PauseTodo(u32),
+    ResumeTodo(u32),
     ToggleStatus(u32),
+    ShowTrash,
+    RestoreTodo(u32),
+    EmptyTrash,
+    Undo,
+    Redo,
     UpdateInput(String),
     ClearInput,
+    EditInput(Edit),
+    // Up and Down in the input: step through earlier commands
+    RecallOlder,
+    RecallNewer,
+    RememberCommand(String),
+    // Tab in the input: fill in the word at the cursor or offer candidates
+    Complete(Completion),
+    // Tab and Shift-Tab while candidates are shown
+    CycleCompletion(isize),
+    CloseCompletion,
+    SelectNext,
+    SelectPrevious,
+    // Puts the cursor on a row, e.g. after a click
+    SelectRow(usize),
+    // Moves the view and the cursor together, for the scroll wheel
+    ScrollTable(isize),
+    // Moves the output log back (positive) or forward in time
+    ScrollOutput(isize),
+    ToggleFocus,
+    FocusInput,
     SetOutput(String),
+    Tick,
+    DismissBanner,
+    ToggleStats,
+    ShowLists,
+    NewList(String),
+    SwitchList(String),
+    InitProjectList,
     NoOp,
 }
 
-pub fn reduce(state: &mut AppState, action: Action) -> () {
+impl Action {
+    // Whether this action can modify the stored todo list
+    pub fn changes_todos(&self) -> bool {
+        matches!(
+            self,
+            Action::AddTodo(_) |
+                Action::EditTodo(..) |
+                Action::RemoveTodo(_) |
+                Action::CompleteTodo(_) |
+                Action::StartTodo(_) |
+                Action::StartTodoFor(..) |
+                Action::StopTodo(_) |
+                Action::PauseTodo(_) |
+                Action::ResumeTodo(_) |
+                Action::ToggleStatus(_) |
+                Action::RestoreTodo(_) |
+                Action::EmptyTrash |
+                Action::Undo |
+                Action::Redo
+        )
+    }
+
+    // Whether this action should be recorded so it can be undone
+    pub fn is_undoable(&self) -> bool {
+        self.changes_todos() && !matches!(self, Action::Undo | Action::Redo)
+    }
+}
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    if action.changes_todos() {
+        state.dirty = true;
+    }
     match action {
         Action::Quit => {
             state.should_quit = true;
         }
         Action::AddTodo(text) => commands::add::run(state, &text),
+        Action::EditTodo(id, text) => commands::edit::run(state, id, &text),
+        Action::BeginEdit(id) => commands::edit::prefill(state, id),
         Action::RemoveTodo(id) => commands::remove::run(state, id),
         Action::CompleteTodo(id) => commands::complete::run(state, id),
-        Action::StartTodo(id) => commands::start::run(state, id),
+        Action::StartTodo(id) => commands::start::run(state, id, None),
+        Action::StartTodoFor(id, secs) => commands::start::run(state, id, Some(secs)),
         Action::StopTodo(id) => commands::stop::run(state, id),
+        Action::PauseTodo(id) => commands::pause::run(state, id),
+        Action::ResumeTodo(id) => commands::resume::run(state, id),
         Action::ToggleStatus(id) => commands::toggleStatus::run(state, id),
-        Action::UpdateInput(s) => {
-            state.input_buffer = s;
+        Action::ShowTrash => commands::trash::show(state),
+        Action::RestoreTodo(id) => commands::trash::restore(state, id),
+        Action::EmptyTrash => commands::trash::empty(state),
+        Action::Undo => commands::undo::undo(state),
+        Action::Redo => commands::undo::redo(state),
+        Action::UpdateInput(s) => state.set_input(s),
+        Action::ClearInput => state.set_input(String::new()),
+        Action::EditInput(edit) => {
+            state.input_cursor = editor::apply(edit, &mut state.input_buffer, state.input_cursor);
+        }
+        Action::RecallOlder => {
+            if let Some(command) = state.command_history.older(&state.input_buffer).map(String::from) {
+                state.set_input(command);
+            }
+        }
+        Action::RecallNewer => {
+            if let Some(command) = state.command_history.newer().map(String::from) {
+                state.set_input(command);
+            }
+        }
+        Action::RememberCommand(command) => {
+            if state.command_history.push(&command) {
+                state.effects.push(Effect::SaveCommand(command.trim().to_string()));
+            }
         }
-        Action::ClearInput => {
-            state.input_buffer.clear();
+        Action::Complete(completion) => completion::show(state, completion),
+        Action::CycleCompletion(step) => completion::cycle(state, step),
+        Action::CloseCompletion => {
+            state.completion = None;
+        }
+        Action::SelectNext => {
+            if let Some(i) = state.selected_index() {
+                state.selected = (i + 1).min(state.todos.len() - 1);
+            }
+        }
+        Action::SelectPrevious => {
+            if let Some(i) = state.selected_index() {
+                state.selected = i.saturating_sub(1);
+            }
+        }
+        Action::SelectRow(index) => {
+            if index < state.todos.len() {
+                state.selected = index;
+                state.focus = Focus::Table;
+            }
+        }
+        Action::ScrollTable(delta) => {
+            if let Some(i) = state.selected_index() {
+                state.table_offset = state.table_offset.saturating_add_signed(delta).min(state.todos.len() - 1);
+                state.selected = i.saturating_add_signed(delta).min(state.todos.len() - 1);
+            }
+        }
+        Action::ScrollOutput(delta) => state.log.scroll_by(delta),
+        Action::ToggleFocus => {
+            state.focus = match state.focus {
+                Focus::Input => Focus::Table,
+                Focus::Table => Focus::Input,
+            };
+        }
+        Action::FocusInput => {
+            state.focus = Focus::Input;
         }
         Action::SetOutput(s) => {
             state.output_buffer = s;
         }
+        Action::Tick => commands::tick::run(state),
+        Action::DismissBanner => {
+            state.banner = None;
+        }
+        Action::ToggleStats => commands::stats::run(state),
+        Action::ShowLists => state.effects.push(Effect::ShowLists),
+        Action::NewList(name) => switch_list(state, name, true),
+        Action::SwitchList(name) => switch_list(state, name, false),
+        Action::InitProjectList => state.effects.push(Effect::InitProjectList),
         Action::NoOp => {}
     }
 }
 
+fn switch_list(state: &mut AppState, name: String, create: bool) {
+    // `project` can't be created, only switched to once it exists
+    let allowed = lists::is_valid_name(&name) || (!create && name == lists::PROJECT);
+    if allowed {
+        state.effects.push(Effect::SwitchList { name, create });
+    } else {
+        state.fail(format!("❌ Invalid list name '{}'. Use letters, digits, - and _.", name));
+    }
+}
+
+// Parses without knowing the list, so selectors like `all` match nothing
 pub fn parse_command(input: &str) -> OneOrMany<Action> {
-    let tokens = input.split_whitespace();
-    let mut int_args = Vec::new();
-    let mut str_args = Vec::new();
+    parse_command_for(input, &[])
+}
 
-    for token in tokens {
-        if let Ok(num) = token.parse::<u32>() {
-            int_args.push(num);
-        } else {
-            str_args.push(token);
+// Selectors such as `3-7`, `completed` or `/regex/` are resolved against `todos`
+pub fn parse_command_for(input: &str, todos: &[Todo]) -> OneOrMany<Action> {
+    let tokens = match tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            return OneOrMany::One(Action::SetOutput(format!("❌ Could not read command: {}", e)));
         }
-    }
-
-    let command = if !str_args.is_empty() { str_args.remove(0).to_string() } else { String::new() };
+    };
+    let Some((first, args)) = tokens.split_first() else {
+        return OneOrMany::One(Action::SetOutput("Invalid command".to_string()));
+    };
 
-    if command.is_empty() && !int_args.is_empty() {
-        return OneOrMany::Many(
-            int_args
-                .iter()
-                .map(|&int| Action::ToggleStatus(int))
-                .collect()
-        );
+    // A bare list of ids or ranges toggles them; a backwards range gets its own error
+    if matches!(Selector::parse(first), Ok(Selector::Id(_) | Selector::Range(..)) | Err(SelectorError::BackwardsRange(..))) {
+        return with_ids(&tokens, todos, "Usage: <id> [<id> ...]", Action::ToggleStatus);
     }
 
-    let actions = match command.as_str() {
+    match keyword(first).unwrap_or_default() {
         "quit" | "exit" | "q" => OneOrMany::One(Action::Quit),
         "add" => {
-            if !str_args.is_empty() || !int_args.is_empty() {
-                let mut all_args = Vec::new();
-                for i in int_args.iter() {
-                    all_args.push(i.to_string());
-                }
-                for s in str_args.iter() {
-                    all_args.push(s.to_string());
-                }
-                OneOrMany::One(Action::AddTodo(all_args.join(" ")))
-            } else {
-                OneOrMany::One(Action::SetOutput("Usage: add <todo text>".to_string()))
+            let words: Vec<&str> = args
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect();
+            let text = words.join(" ");
+            // Quotes let `add ""` through the tokenizer, so check the text itself
+            if text.trim().is_empty() {
+                return OneOrMany::One(Action::SetOutput("Usage: add <todo text>".to_string()));
             }
+            OneOrMany::One(Action::AddTodo(text))
         }
-        "remove" | "rm" => {
-            if int_args.is_empty() {
-                OneOrMany::One(Action::SetOutput("Usage: remove <id> [<id> ...]".to_string()))
-            } else {
-                OneOrMany::Many(int_args.into_iter().map(Action::RemoveTodo).collect())
+        "edit" => {
+            let usage = "Usage: edit <id> [<new text>]";
+            let Some((target, text)) = args.split_first() else {
+                return OneOrMany::One(Action::SetOutput(usage.to_string()));
+            };
+            let Ok(Selector::Id(id)) = Selector::parse(target) else {
+                return OneOrMany::One(Action::SetOutput(unexpected(target, "an id", usage)));
+            };
+            if text.is_empty() {
+                return OneOrMany::One(Action::BeginEdit(id));
             }
-        }
-        "complete" | "done" => {
-            if int_args.is_empty() {
-                OneOrMany::One(Action::SetOutput("Usage: complete <id> [<id> ...]".to_string()))
-            } else {
-                OneOrMany::Many(int_args.into_iter().map(Action::CompleteTodo).collect())
+            let words: Vec<&str> = text
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect();
+            let text = words.join(" ");
+            if text.trim().is_empty() {
+                return OneOrMany::One(Action::SetOutput(usage.to_string()));
             }
+            OneOrMany::One(Action::EditTodo(id, text))
         }
+        "remove" | "rm" => with_ids(args, todos, "Usage: remove <id> [<id> ...]", Action::RemoveTodo),
+        "complete" | "done" => with_ids(args, todos, "Usage: complete <id> [<id> ...]", Action::CompleteTodo),
         "start" => {
-            if int_args.is_empty() {
-                OneOrMany::One(Action::SetOutput("Usage: start <id> [<id> ...]".to_string()))
-            } else {
-                OneOrMany::Many(int_args.into_iter().map(Action::StartTodo).collect())
+            let usage = "Usage: start <id> [<id> ...] [<duration>]";
+            let (ids, rest): (Vec<&Token>, Vec<&Token>) = args
+                .iter()
+                .partition(|t| !matches!(Selector::parse(t), Err(SelectorError::NotASelector)));
+            match rest[..] {
+                [] => with_ids(&ids, todos, usage, Action::StartTodo),
+                [duration] => {
+                    let Some(secs) = keyword(duration).and_then(parse_duration) else {
+                        return OneOrMany::One(Action::SetOutput(unexpected(duration, "a duration", usage)));
+                    };
+                    with_ids(&ids, todos, usage, |id| Action::StartTodoFor(id, secs))
+                }
+                [_, extra, ..] => OneOrMany::One(Action::SetOutput(unexpected(extra, "an id", usage))),
             }
         }
-        "stop" => {
-            if int_args.is_empty() {
-                OneOrMany::One(Action::SetOutput("Usage: stop <id> [<id> ...]".to_string()))
-            } else {
-                OneOrMany::Many(int_args.into_iter().map(Action::StopTodo).collect())
-            }
+        "stop" => with_ids(args, todos, "Usage: stop <id> [<id> ...]", Action::StopTodo),
+        "stats" => OneOrMany::One(Action::ToggleStats),
+        "trash" => OneOrMany::One(Action::ShowTrash),
+        // Trashed todos aren't in `todos`, so only plain ids make sense here
+        "restore" => with_ids(args, &[], "Usage: restore <id> [<id> ...]", Action::RestoreTodo),
+        "empty-trash" => OneOrMany::One(Action::EmptyTrash),
+        "undo" => OneOrMany::One(Action::Undo),
+        "redo" => OneOrMany::One(Action::Redo),
+        "list" => {
+            let action = match args {
+                [] => Action::ShowLists,
+                [sub] if keyword(sub) == Some("init") => Action::InitProjectList,
+                [sub, name] if keyword(sub) == Some("new") => Action::NewList(name.text.clone()),
+                [sub, name] if keyword(sub) == Some("switch") => Action::SwitchList(name.text.clone()),
+                _ => Action::SetOutput("Usage: list [init | new <name> | switch <name>]".to_string()),
+            };
+            OneOrMany::One(action)
         }
+        "pause" => with_ids(args, todos, "Usage: pause <id> [<id> ...]", Action::PauseTodo),
+        "resume" => with_ids(args, todos, "Usage: resume <id> [<id> ...]", Action::ResumeTodo),
         _ => OneOrMany::One(Action::SetOutput("Invalid command".to_string())),
-    };
+    }
+}
+
+// The token's text when it can act as a keyword or duration, i.e. wasn't quoted
+fn keyword(token: &Token) -> Option<&str> {
+    (!token.literal).then_some(token.text.as_str())
+}
+
+// One action per selected id, or a message pointing at the first token that
+// isn't a selector
+fn with_ids<T: Borrow<Token>>(
+    tokens: &[T],
+    todos: &[Todo],
+    usage: &str,
+    action: impl Fn(u32) -> Action
+) -> OneOrMany<Action> {
+    if tokens.is_empty() {
+        return OneOrMany::One(Action::SetOutput(usage.to_string()));
+    }
+    let mut selectors = Vec::new();
+    for token in tokens.iter().map(|t| t.borrow()) {
+        match Selector::parse(token) {
+            Ok(selector) => selectors.push(selector),
+            Err(SelectorError::NotASelector) => {
+                return OneOrMany::One(Action::SetOutput(unexpected(token, "an id", usage)));
+            }
+            Err(e) => {
+                return OneOrMany::One(Action::SetOutput(format!("❌ At column {}: {}.", token.column, e)));
+            }
+        }
+    }
+    let ids = select(&selectors, todos);
+    if ids.is_empty() {
+        let asked: Vec<&str> = tokens
+            .iter()
+            .map(|t| t.borrow().text.as_str())
+            .collect();
+        return OneOrMany::One(Action::SetOutput(format!("❌ No todos match '{}'.", asked.join(" "))));
+    }
+    OneOrMany::Many(ids.into_iter().map(action).collect())
+}
 
-    actions
+fn unexpected(token: &Token, expected: &str, usage: &str) -> String {
+    format!("❌ Expected {} at column {}, found '{}'. {}", expected, token.column, token.text, usage)
 }
@@ -0,0 +1,105 @@
+use regex::Regex;
+
+use crate::models::todo::{ Todo, TodoStatus };
+use crate::tokenizer::Token;
+
+// Picks todos for bulk commands:
+//   3        one id          3-7        every id in the range
+//   all, *   every todo      completed  every todo with that status
+//   +backend todos tagged +backend in their description
+//   /regex/  todos whose description matches
+#[derive(Debug, Clone)]
+pub enum Selector {
+    Id(u32),
+    Range(u32, u32),
+    All,
+    Status(TodoStatus),
+    Tag(String),
+    Pattern(Regex),
+}
+
+#[derive(Debug)]
+pub enum SelectorError {
+    // The token isn't selector syntax at all, e.g. a stray word
+    NotASelector,
+    BackwardsRange(u32, u32),
+    BadPattern(regex::Error),
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::NotASelector => write!(f, "not an id or selector"),
+            SelectorError::BackwardsRange(from, to) => write!(f, "range {}-{} runs backwards, try {}-{}", from, to, to, from),
+            SelectorError::BadPattern(e) => write!(f, "invalid pattern: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl Selector {
+    // Quoted tokens are only read as patterns, so "/fix the/" works but "3" stays text
+    pub fn parse(token: &Token) -> Result<Self, SelectorError> {
+        let text = token.text.as_str();
+        if let Some(pattern) = text.strip_prefix('/').and_then(|t| t.strip_suffix('/')) {
+            return Regex::new(pattern).map(Selector::Pattern).map_err(SelectorError::BadPattern);
+        }
+        if token.literal {
+            return Err(SelectorError::NotASelector);
+        }
+        if let Ok(id) = text.parse() {
+            return Ok(Selector::Id(id));
+        }
+        if let Some((from, to)) = text.split_once('-') && let (Ok(from), Ok(to)) = (from.parse(), to.parse()) {
+            return if from <= to { Ok(Selector::Range(from, to)) } else { Err(SelectorError::BackwardsRange(from, to)) };
+        }
+        if let Some(tag) = text.strip_prefix('+') && !tag.is_empty() {
+            return Ok(Selector::Tag(tag.to_lowercase()));
+        }
+        match text {
+            "all" | "*" => Ok(Selector::All),
+            "pending" => Ok(Selector::Status(TodoStatus::Pending)),
+            "active" | "in-progress" => Ok(Selector::Status(TodoStatus::InProgress)),
+            "completed" | "done" => Ok(Selector::Status(TodoStatus::Completed)),
+            _ => Err(SelectorError::NotASelector),
+        }
+    }
+
+    pub fn matches(&self, todo: &Todo) -> bool {
+        match self {
+            Selector::Id(id) => todo.id == *id,
+            Selector::Range(from, to) => (*from..=*to).contains(&todo.id),
+            Selector::All => true,
+            Selector::Status(status) => todo.status == *status,
+            Selector::Tag(tag) =>
+                todo.description
+                    .split_whitespace()
+                    .any(|word| word.strip_prefix('+').is_some_and(|w| w.to_lowercase() == *tag)),
+            Selector::Pattern(regex) => regex.is_match(&todo.description),
+        }
+    }
+}
+
+// The ids picked by `selectors`, in the order they were asked for, each once.
+// Plain ids are kept even when missing so the command can say so.
+pub fn select(selectors: &[Selector], todos: &[Todo]) -> Vec<u32> {
+    let mut ids = Vec::new();
+    for selector in selectors {
+        let picked: Vec<u32> = match selector {
+            Selector::Id(id) => vec![*id],
+            _ =>
+                todos
+                    .iter()
+                    .filter(|t| selector.matches(t))
+                    .map(|t| t.id)
+                    .collect(),
+        };
+        for id in picked {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
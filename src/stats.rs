@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::models::history::{ HistoryEntry, Outcome };
+use crate::utils::{ civil_date, local_day };
+
+pub const DAYS_SHOWN: usize = 14;
+pub const WEEKS_SHOWN: usize = 8;
+
+// Aggregates over the history log; days are local calendar days and weeks start on Monday
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    // Finished pomodoros per day, oldest first, ending today
+    pub per_day: Vec<u64>,
+    // Finished pomodoros per week, oldest first, ending this week
+    pub per_week: Vec<u64>,
+    // (todo id, description, focused seconds), most time first
+    pub per_todo: Vec<(u32, String, u64)>,
+    pub today: u64,
+    pub total: u64,
+    pub focused_seconds: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+}
+
+impl Stats {
+    pub fn from_history(entries: &[HistoryEntry], now: u64) -> Self {
+        Self::from_history_by(entries, now, local_day)
+    }
+
+    // Same, with `day_of` turning a time into the calendar day it falls on
+    pub fn from_history_by(entries: &[HistoryEntry], now: u64, day_of: impl Fn(u64) -> u64) -> Self {
+        let today = day_of(now);
+        let this_week = week_of(today);
+
+        let mut days: HashMap<u64, u64> = HashMap::new();
+        let mut todos: HashMap<u32, (String, u64)> = HashMap::new();
+        let mut focused_seconds = 0;
+        for entry in entries {
+            focused_seconds += entry.duration;
+            let todo = todos.entry(entry.todo_id).or_insert_with(|| (String::new(), 0));
+            todo.0 = entry.description.clone();
+            todo.1 += entry.duration;
+            if entry.outcome == Outcome::Finished {
+                *days.entry(day_of(entry.start)).or_insert(0) += 1;
+            }
+        }
+
+        let per_day = (0..DAYS_SHOWN as u64)
+            .rev()
+            .map(|offset| today.checked_sub(offset).and_then(|d| days.get(&d)).copied().unwrap_or(0))
+            .collect();
+
+        let mut weeks: HashMap<u64, u64> = HashMap::new();
+        for (day, count) in &days {
+            *weeks.entry(week_of(*day)).or_insert(0) += count;
+        }
+        let per_week = (0..WEEKS_SHOWN as u64)
+            .rev()
+            .map(|offset| this_week.checked_sub(offset).and_then(|w| weeks.get(&w)).copied().unwrap_or(0))
+            .collect();
+
+        let mut per_todo: Vec<(u32, String, u64)> = todos
+            .into_iter()
+            .map(|(id, (description, secs))| (id, description, secs))
+            .collect();
+        per_todo.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+
+        let (current_streak, best_streak) = streaks(&days, today);
+
+        Self {
+            per_day,
+            per_week,
+            per_todo,
+            today: days.get(&today).copied().unwrap_or(0),
+            total: days.values().sum(),
+            focused_seconds,
+            current_streak,
+            best_streak,
+        }
+    }
+
+    // Short label such as "10-18" for the day `offset` days before `now`
+    pub fn day_label(now: u64, offset: u64) -> String {
+        let (_, month, day) = civil_date(local_day(now).saturating_sub(offset) as i64);
+        format!("{:02}-{:02}", month, day)
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "📊 Today: {} 🍅 | Total: {} 🍅 | Focused: {}h{:02}m | Streak: {} days (best {})",
+            self.today,
+            self.total,
+            self.focused_seconds / 3600,
+            (self.focused_seconds % 3600) / 60,
+            self.current_streak,
+            self.best_streak
+        )
+    }
+}
+
+// 1970-01-01 was a Thursday, so shift by three days to start weeks on Monday
+fn week_of(day: u64) -> u64 {
+    (day + 3) / 7
+}
+
+// A streak is a run of consecutive days with at least one finished pomodoro.
+// Today not having one yet doesn't break the current streak.
+fn streaks(days: &HashMap<u64, u64>, today: u64) -> (u32, u32) {
+    let mut sorted: Vec<u64> = days.keys().copied().collect();
+    sorted.sort_unstable();
+
+    let mut best = 0;
+    let mut run = 0;
+    let mut previous: Option<u64> = None;
+    for day in &sorted {
+        run = match previous {
+            Some(p) if p + 1 == *day => run + 1,
+            _ => 1,
+        };
+        best = best.max(run);
+        previous = Some(*day);
+    }
+
+    let mut current = 0;
+    let mut day = if days.contains_key(&today) { Some(today) } else { today.checked_sub(1) };
+    while let Some(d) = day.filter(|d| days.contains_key(d)) {
+        current += 1;
+        day = d.checked_sub(1);
+    }
+
+    (current, best)
+}
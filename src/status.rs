@@ -0,0 +1,75 @@
+use regex::{ Captures, Regex };
+use serde::Serialize;
+
+use crate::models::cycle::Phase;
+use crate::models::todo::{ Todo, TodoStatus };
+use crate::output::phase_name;
+
+pub const DEFAULT_TEMPLATE: &str = "{icon} {remaining} {description}";
+
+// The todo a status line should show: the first in-progress todo with a
+// running timer, falling back to a paused one
+pub fn active(todos: &[Todo]) -> Option<&Todo> {
+    let mut timed = todos
+        .iter()
+        .filter(|t| t.status == TodoStatus::InProgress && t.timer.is_some());
+    let first = timed.clone().next();
+    timed.find(|t| t.timer.as_ref().is_some_and(|timer| !timer.is_paused())).or(first)
+}
+
+pub fn icon(todo: &Todo) -> &'static str {
+    match &todo.timer {
+        Some(timer) if timer.is_paused() => "⏸️",
+        Some(timer) if timer.phase() != Phase::Work => "☕",
+        _ => "🍅",
+    }
+}
+
+// Fills in {icon}, {remaining}, {seconds}, {description}, {id}, {phase} and
+// {round}; unknown placeholders are left as they are
+pub fn render(template: &str, todo: &Todo) -> String {
+    let (remaining, phase, round) = match &todo.timer {
+        Some(timer) => (timer.remaining_seconds(), timer.phase().to_string(), timer.round()),
+        None => (0, String::new(), 0),
+    };
+    // One pass over the template, so placeholders inside the description stay as typed
+    let placeholder = Regex::new(r"\{(\w+)\}").expect("placeholder pattern is valid");
+    placeholder
+        .replace_all(template, |caps: &Captures| {
+            match &caps[1] {
+                "icon" => icon(todo).to_string(),
+                "remaining" => format!("{:02}:{:02}", remaining / 60, remaining % 60),
+                "seconds" => remaining.to_string(),
+                "description" => todo.description.clone(),
+                "id" => todo.id.to_string(),
+                "phase" => phase.clone(),
+                "round" => round.to_string(),
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+// Custom module output understood by waybar (and polybar scripts that parse it)
+#[derive(Debug, Serialize)]
+pub struct Waybar {
+    pub text: String,
+    pub tooltip: String,
+    pub class: &'static str,
+    pub percentage: u64,
+}
+
+impl Waybar {
+    pub fn new(todo: Option<&Todo>, text: String) -> Self {
+        let Some((todo, timer)) = todo.and_then(|t| t.timer.as_ref().map(|timer| (t, timer))) else {
+            return Self { text, tooltip: "No timer running".to_string(), class: "idle", percentage: 0 };
+        };
+        let elapsed = timer.duration().saturating_sub(timer.remaining_seconds());
+        Self {
+            tooltip: format!("#{} {} ({}, round {})", todo.id, todo.description, timer.phase(), timer.round()),
+            text,
+            class: if timer.is_paused() { "paused" } else { phase_name(timer.phase()) },
+            percentage: (elapsed * 100).checked_div(timer.duration()).unwrap_or(100),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::env;
+use std::fs::{ self, File };
+use std::io::{ self, Write };
+use std::path::{ Path, PathBuf };
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use crate::models::history::HistoryEntry;
+use crate::models::todo::{ LoadError, Todo, TodoFile, TODOS_FILE };
+
+pub const FILE_ENV: &str = "POMONOTE_FILE";
+pub const PROJECT_FILE: &str = ".pomonote.json";
+pub const BACKUPS_KEPT: usize = 3;
+
+// $XDG_DATA_HOME/pomonote, falling back to ~/.local/share/pomonote
+pub fn data_dir() -> Option<PathBuf> {
+    let base = env
+        ::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(base.join("pomonote"))
+}
+
+pub fn default_path() -> PathBuf {
+    data_dir().map_or_else(|| PathBuf::from(TODOS_FILE), |dir| dir.join(TODOS_FILE))
+}
+
+// `--file` wins over $POMONOTE_FILE, then a project's `.pomonote.json`, then the data dir
+pub fn resolve(file: Option<PathBuf>, project: Option<&Path>) -> PathBuf {
+    file.or_else(|| {
+        env::var_os(FILE_ENV)
+            .filter(|f| !f.is_empty())
+            .map(PathBuf::from)
+    })
+        .or_else(|| project.map(Path::to_path_buf))
+        .unwrap_or_else(default_path)
+}
+
+// Walks up from `start` looking for a project-local todo list
+pub fn discover_project(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+// Where `list init` puts a new project list: the enclosing git root, or `start` itself
+pub fn project_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+// Moves a `todos.json` left in the working directory by older versions to `target`,
+// along with its history log. Files that aren't a pomonote list, e.g. another
+// tool's `todos.json`, are left alone. Returns the full path of the moved file.
+pub fn migrate_legacy(legacy: &Path, target: &Path) -> io::Result<Option<PathBuf>> {
+    if !legacy.is_file() || target.exists() || same_file(legacy, target) || Todo::load_file(legacy).is_err() {
+        return Ok(None);
+    }
+    let moved = fs::canonicalize(legacy)?;
+
+    if let Some(parent) = target.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    move_file(legacy, target)?;
+
+    let legacy_history = HistoryEntry::path_for(legacy);
+    let target_history = HistoryEntry::path_for(target);
+    if legacy_history.is_file() && !target_history.exists() {
+        move_file(&legacy_history, &target_history)?;
+    }
+
+    Ok(Some(moved))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// rename() fails across filesystems, so fall back to copy and remove
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+// Writes to a temporary file in the same directory and renames it over `path`,
+// so readers never see a half-written file even if we crash mid-save
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "todos".to_string());
+    let tmp = dir.join(format!(".{}.tmp-{}", name, std::process::id()));
+
+    let result = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result?;
+
+    // Persist the rename itself; not every platform lets us open a directory
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.bak.{}", name, n))
+}
+
+// Copies `path` to `<path>.bak.1`, shifting older backups up and dropping the oldest
+pub fn rotate_backups(path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 || !path.is_file() {
+        return Ok(());
+    }
+    for n in (1..keep).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, n + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+// Renames a corrupt file to `<name>.corrupt-<timestamp>` so it can be repaired by hand
+pub fn quarantine(path: &Path) -> io::Result<PathBuf> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let target = path.with_file_name(format!("{}.corrupt-{}", name, now));
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+// Loads `path`, moving a corrupt file aside first. Ok carries an optional warning for the
+// user; Err means the file is still in place and must not be saved over.
+pub fn load_or_quarantine(path: &Path) -> Result<(TodoFile, Option<String>), String> {
+    match Todo::load_file(path) {
+        Ok(file) => Ok((file, None)),
+        Err(LoadError::Parse(e)) => {
+            let error = LoadError::Parse(e);
+            match quarantine(path) {
+                Ok(moved) =>
+                    Ok((
+                        TodoFile::default(),
+                        Some(
+                            format!(
+                                "❌ {} is corrupt ({}). Moved it to {} and started an empty list.",
+                                path.display(),
+                                error,
+                                moved.display()
+                            )
+                        ),
+                    )),
+                Err(qe) =>
+                    Err(
+                        format!(
+                            "❌ {} is corrupt ({}) and could not be moved aside ({}). Changes will not be saved.",
+                            path.display(),
+                            error,
+                            qe
+                        )
+                    ),
+            }
+        }
+        Err(error) =>
+            Err(format!("❌ {}: {}. Changes will not be saved.", path.display(), error)),
+    }
+}
+
+pub fn lock_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.lock", name))
+}
+
+// Advisory lock on `<name>.lock` next to a todo file, held by whichever process
+// may write the file. Released when dropped or when the process exits.
+#[derive(Debug)]
+pub struct Lock {
+    _file: File,
+}
+
+#[derive(Debug)]
+pub enum LockError {
+    // Another process holds the lock; carries its pid when it could be read
+    Held(Option<u32>),
+    Io(io::Error),
+}
+
+impl std::fmt::Display for LockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockError::Held(Some(pid)) => write!(f, "it is open in another pomonote (pid {})", pid),
+            LockError::Held(None) => write!(f, "it is open in another pomonote"),
+            LockError::Io(e) => write!(f, "could not lock it: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LockError {}
+
+impl Lock {
+    pub fn acquire(path: &Path) -> Result<Self, LockError> {
+        let lock_path = lock_path(path);
+        if let Some(dir) = lock_path.parent() && !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir).map_err(LockError::Io)?;
+        }
+        // Not truncated on open: the holder's pid must survive a failed attempt
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(LockError::Io)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(fs::TryLockError::WouldBlock) => {
+                let pid = fs::read_to_string(&lock_path).ok().and_then(|s| s.trim().parse().ok());
+                return Err(LockError::Held(pid));
+            }
+            Err(fs::TryLockError::Error(e)) => {
+                return Err(LockError::Io(e));
+            }
+        }
+        file.set_len(0).map_err(LockError::Io)?;
+        write!(file, "{}", std::process::id()).map_err(LockError::Io)?;
+        Ok(Self { _file: file })
+    }
+}
+
+// Enough of a file's metadata to notice that another program wrote it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+pub fn stamp(path: &Path) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    Some(Stamp { modified: meta.modified().ok()?, len: meta.len() })
+}
@@ -0,0 +1,126 @@
+// Splits a command line into words the way a shell would, minus expansions:
+//   add "Fix the main bug"    -> add | Fix the main bug
+//   add it\'s done            -> add | it's | done
+//   add -- 42                 -> add | 42 (literal, never read as an id)
+// Double quotes allow \" and \\ escapes, single quotes are taken verbatim.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    // 1-based character column where the token starts, including any quote
+    pub column: usize,
+    // Quoted, escaped or after `--`: plain text, never read as an id, duration or keyword
+    pub literal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    // A quote with no closing partner; column of the opening quote
+    UnterminatedQuote { quote: char, column: usize },
+    // A backslash at the very end of the input
+    TrailingEscape { column: usize },
+}
+
+impl TokenError {
+    pub fn column(&self) -> usize {
+        match self {
+            TokenError::UnterminatedQuote { column, .. } | TokenError::TrailingEscape { column } => *column,
+        }
+    }
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::UnterminatedQuote { quote, column } =>
+                write!(f, "unterminated {} at column {}", quote, column),
+            TokenError::TrailingEscape { column } => write!(f, "nothing to escape after \\ at column {}", column),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, TokenError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().zip(1..).peekable();
+    let mut literal_rest = false;
+
+    while let Some(&(c, column)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut text = String::new();
+        // Any quoting or escaping in the word
+        let mut quoted = false;
+        while let Some(&(c, at)) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            match c {
+                '\\' => {
+                    quoted = true;
+                    let (escaped, _) = chars.next().ok_or(TokenError::TrailingEscape { column: at })?;
+                    text.push(escaped);
+                }
+                '\'' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some(('\'', _)) => break,
+                            Some((c, _)) => text.push(c),
+                            None => {
+                                return Err(TokenError::UnterminatedQuote { quote: '\'', column: at });
+                            }
+                        }
+                    }
+                }
+                '"' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some(('"', _)) => break,
+                            Some(('\\', _)) if matches!(chars.peek(), Some(('"' | '\\', _))) => {
+                                text.push(chars.next().unwrap().0);
+                            }
+                            Some((c, _)) => text.push(c),
+                            None => {
+                                return Err(TokenError::UnterminatedQuote { quote: '"', column: at });
+                            }
+                        }
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+
+        // The first bare `--` ends parsing; everything after it is plain text
+        if !literal_rest && !quoted && text == "--" {
+            literal_rest = true;
+            continue;
+        }
+        tokens.push(Token { text, column, literal: quoted || literal_rest });
+    }
+
+    Ok(tokens)
+}
+
+// Backslash-escapes quotes, backslashes and a bare `--` so `text` reads back
+// unchanged, apart from runs of whitespace
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut word_start = true;
+    for (i, c) in text.char_indices() {
+        let rest = &text[i..];
+        let bare_dashes = rest.strip_prefix("--").is_some_and(|r| r.is_empty() || r.starts_with(char::is_whitespace));
+        if matches!(c, '"' | '\'' | '\\') || (word_start && bare_dashes) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        word_start = c.is_whitespace();
+    }
+    escaped
+}
@@ -0,0 +1,121 @@
+use serde::Serialize;
+
+use crate::models::todo::{ Todo, Trashed };
+
+// How many commands can be undone; older ones are dropped
+pub const UNDO_LIMIT: usize = 100;
+
+// The list and trash at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    pub todos: Vec<Todo>,
+    pub trash: Vec<Trashed>,
+}
+
+// The list and trash as they were before and after a command, labelled with what was typed
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub label: String,
+    pub before: Version,
+    pub after: Version,
+}
+
+// Undo and redo history for the session. Each entry covers a whole command,
+// so `rm 1 2 3` comes back in one step.
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+impl Version {
+    pub fn new(todos: &[Todo], trash: &[Trashed]) -> Self {
+        Self { todos: todos.to_vec(), trash: trash.to_vec() }
+    }
+}
+
+impl UndoStack {
+    // Remembers what a command changed. A new change drops anything that was
+    // undone, like in an editor.
+    pub fn record(&mut self, label: &str, before: Version, after: Version) {
+        self.undo.push(Snapshot { label: label.to_string(), before, after });
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    // Takes back what the last command changed and returns its label
+    pub fn undo(&mut self, todos: &mut Vec<Todo>, trash: &mut Vec<Trashed>) -> Option<String> {
+        let snapshot = self.undo.pop()?;
+        *todos = rewind(todos, &snapshot.after.todos, &snapshot.before.todos);
+        *trash = snapshot.before.trash.clone();
+        let label = snapshot.label.clone();
+        self.redo.push(snapshot);
+        Some(label)
+    }
+
+    // Applies the last undone command again and returns its label
+    pub fn redo(&mut self, todos: &mut Vec<Todo>, trash: &mut Vec<Trashed>) -> Option<String> {
+        let snapshot = self.redo.pop()?;
+        *todos = rewind(todos, &snapshot.before.todos, &snapshot.after.todos);
+        *trash = snapshot.after.trash.clone();
+        let label = snapshot.label.clone();
+        self.undo.push(snapshot);
+        Some(label)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+// Turns the todos from `from` back into `to`, in `to`'s order. Only the fields
+// the command changed go back, so timers that kept running since, moving
+// through phases and rounds, are left as they are now.
+fn rewind(current: &[Todo], from: &[Todo], to: &[Todo]) -> Vec<Todo> {
+    fn find(todos: &[Todo], id: u32) -> Option<&Todo> {
+        todos.iter().find(|t| t.id == id)
+    }
+    let mut todos: Vec<Todo> = to
+        .iter()
+        .map(|old| {
+            let (Some(new), Some(now)) = (find(from, old.id), find(current, old.id)) else {
+                return old.clone();
+            };
+            let mut todo = now.clone();
+            if new.description != old.description {
+                todo.description = old.description.clone();
+            }
+            if new.status != old.status {
+                todo.status = old.status.clone();
+            }
+            if !same(&new.timer, &old.timer) {
+                todo.timer = old.timer.clone();
+            }
+            todo
+        })
+        .collect();
+    // Nothing else adds todos between commands, but never drop one
+    todos.extend(
+        current
+            .iter()
+            .filter(|t| find(from, t.id).is_none() && find(to, t.id).is_none())
+            .cloned()
+    );
+    todos
+}
+
+// Whether two values would be stored the same way
+fn same<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
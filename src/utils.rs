@@ -1,5 +1,6 @@
 use std::{ error::Error, io, panic };
 
+use chrono::{ Local, TimeZone };
 use crossterm::{
     event::{ DisableMouseCapture },
     execute,
@@ -40,3 +41,84 @@ pub enum OneOrMany<T> {
     One(T),
     Many(Vec<T>),
 }
+
+impl<T> OneOrMany<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(item) => vec![item],
+            OneOrMany::Many(items) => items,
+        }
+    }
+}
+
+// Parses durations like "50m", "90s", "1h30m"; a bare number is taken as minutes.
+// Zero is rejected, since a timer that is already over would finish on every tick.
+pub fn parse_duration(input: &str) -> Option<u64> {
+    if let Ok(minutes) = input.parse::<u64>() {
+        return minutes.checked_mul(60).filter(|&secs| secs > 0);
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let value: u64 = digits.parse().ok()?;
+        digits.clear();
+        let secs = match c {
+            'h' => value.checked_mul(3600)?,
+            'm' => value.checked_mul(60)?,
+            's' => value,
+            _ => {
+                return None;
+            }
+        };
+        total = total.checked_add(secs)?;
+    }
+
+    if !digits.is_empty() || total == 0 { None } else { Some(total) }
+}
+
+// Seconds the local clock is ahead of UTC at `secs`
+pub fn local_offset(secs: u64) -> i64 {
+    Local.timestamp_opt(secs as i64, 0)
+        .earliest()
+        .map_or(0, |time| time.offset().local_minus_utc() as i64)
+}
+
+// Time of day as "HH:MM:SS" on the local clock
+pub fn clock(secs: u64) -> String {
+    clock_at(secs, local_offset(secs))
+}
+
+// Time of day `offset` seconds ahead of UTC
+pub fn clock_at(secs: u64, offset: i64) -> String {
+    let secs = ((secs as i64) + offset).rem_euclid(86_400);
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
+
+// Days since 1970-01-01 on the local calendar, so days roll over at local midnight
+pub fn local_day(secs: u64) -> u64 {
+    day_at(secs, local_offset(secs))
+}
+
+// Days since 1970-01-01 `offset` seconds ahead of UTC
+pub fn day_at(secs: u64, offset: i64) -> u64 {
+    ((secs as i64) + offset).max(0) as u64 / 86_400
+}
+
+// Converts days since the Unix epoch to a (year, month, day) civil date
+pub fn civil_date(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + (if month <= 2 { 1 } else { 0 });
+    (year, month, day)
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use clap::Parser;
+use pomonote::cli::{ self, Cli, Command };
+use pomonote::models::timer::Timer;
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::output::Format;
+use pomonote::storage;
+use pomonote::reduce::{ Action, AppState };
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::ExitCode;
+
+fn temp_file(name: &str) -> PathBuf {
+    common::temp_dir("cli", name).join("todos.json")
+}
+
+fn state_for(file: &Path) -> AppState {
+    let mut state = AppState::new(Todo::load_all(file).unwrap());
+    state.file = file.to_path_buf();
+    state
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from(["pomonote", "add", "buy", "milk"]).unwrap();
+        assert_eq!(cli.command, Some(Command::Add { text: vec!["buy".to_string(), "milk".to_string()] }));
+
+        let cli = Cli::try_parse_from(["pomonote", "edit", "2", "buy", "oat", "milk"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Edit { id: 2, text: vec!["buy".to_string(), "oat".to_string(), "milk".to_string()] })
+        );
+
+        let cli = Cli::try_parse_from(["pomonote", "complete", "1", "2"]).unwrap();
+        assert_eq!(cli.command, Some(Command::Done { ids: vec![1, 2] }));
+
+        let cli = Cli::try_parse_from(["pomonote", "start", "3", "-d", "50m"]).unwrap();
+        assert_eq!(cli.command, Some(Command::Start { ids: vec![3], duration: Some(3000) }));
+
+        let cli = Cli::try_parse_from(["pomonote", "ls", "--file", "/tmp/x.json"]).unwrap();
+        assert_eq!(cli.command, Some(Command::Ls));
+        assert_eq!(cli.file, Some(PathBuf::from("/tmp/x.json")));
+
+        let cli = Cli::try_parse_from(["pomonote", "status", "--waybar", "-t", "{remaining}"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Status { template: "{remaining}".to_string(), empty: String::new(), waybar: true })
+        );
+
+        assert_eq!(Cli::try_parse_from(["pomonote"]).unwrap().command, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_arguments() {
+        assert!(Cli::try_parse_from(["pomonote", "done"]).is_err());
+        assert!(Cli::try_parse_from(["pomonote", "rm", "abc"]).is_err());
+        assert!(Cli::try_parse_from(["pomonote", "edit", "2"]).is_err());
+        assert!(Cli::try_parse_from(["pomonote", "start", "1", "--duration", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_actions() {
+        let start = Command::Start { ids: vec![1, 2], duration: None };
+        let actions = start.actions();
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(actions[0], Action::StartTodo(1)));
+        assert!(matches!(actions[1], Action::StartTodo(2)));
+        let timed = Command::Start { ids: vec![4], duration: Some(600) };
+        assert!(matches!(timed.actions()[0], Action::StartTodoFor(4, 600)));
+        let edit = Command::Edit { id: 3, text: vec!["new".to_string(), "text".to_string()] };
+        assert!(matches!(&edit.actions()[..], [Action::EditTodo(3, text)] if text == "new text"));
+        assert!(matches!(Command::Restore { ids: vec![2] }.actions()[..], [Action::RestoreTodo(2)]));
+        assert!(Command::Ls.actions().is_empty());
+        assert!(Command::Trash.actions().is_empty());
+    }
+
+    #[test]
+    fn test_run_saves_changes() {
+        let file = temp_file("saves");
+        let add = Command::Add { text: vec!["write".to_string(), "docs".to_string()] };
+        assert_eq!(cli::run(state_for(&file), add, Format::Text), ExitCode::SUCCESS);
+        assert_eq!(cli::run(state_for(&file), Command::Done { ids: vec![1] }, Format::Text), ExitCode::SUCCESS);
+
+        let todos = Todo::load_all(&file).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].description, "write docs");
+        assert_eq!(todos[0].status, TodoStatus::Completed);
+    }
+
+    #[test]
+    fn test_run_fails_for_unknown_id() {
+        let file = temp_file("unknown");
+        assert_eq!(cli::run(state_for(&file), Command::Rm { ids: vec![7] }, Format::Text), ExitCode::FAILURE);
+    }
+
+    #[test]
+    fn test_run_refuses_changes_when_saves_blocked() {
+        let file = temp_file("blocked");
+        let mut state = state_for(&file);
+        state.save_blocked = Some("⚠️  corrupt".to_string());
+        let add = Command::Add { text: vec!["lost".to_string()] };
+        assert_eq!(cli::run(state, add, Format::Text), ExitCode::FAILURE);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_run_refuses_changes_while_locked() {
+        let file = temp_file("locked");
+        let _lock = storage::Lock::acquire(&file).unwrap();
+        let add = Command::Add { text: vec!["clobber".to_string()] };
+        assert_eq!(cli::run(state_for(&file), add, Format::Text), ExitCode::FAILURE);
+        assert!(!file.exists());
+        assert_eq!(cli::run(state_for(&file), Command::Ls, Format::Text), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn test_read_only_commands_do_not_write() {
+        let file = temp_file("read-only");
+        let mut todo = Todo::new(1, "finished while away".to_string());
+        let mut timer = Timer::new().with_duration(0);
+        timer.start();
+        todo.timer = Some(timer);
+        Todo::save_all(&file, &[todo]).unwrap();
+        let before = fs::read_to_string(&file).unwrap();
+
+        // Even with the list locked, nothing is ticked or saved
+        let _lock = storage::Lock::acquire(&file).unwrap();
+        assert_eq!(cli::run(state_for(&file), Command::Ls, Format::Text), ExitCode::SUCCESS);
+        assert_eq!(cli::run(state_for(&file), Command::Stats, Format::Json), ExitCode::SUCCESS);
+        assert_eq!(cli::run(state_for(&file), Command::Trash, Format::Text), ExitCode::SUCCESS);
+        assert_eq!(fs::read_to_string(&file).unwrap(), before);
+    }
+}
@@ -0,0 +1,55 @@
+// Helpers shared by the integration tests. Each test binary uses only some of them.
+#![allow(dead_code)]
+
+use crossterm::event::{ KeyCode, KeyEvent, KeyModifiers };
+use pomonote::app;
+use pomonote::display;
+use pomonote::models::todo::Todo;
+use pomonote::reduce::{ parse_command_for, reduce, Action, AppState };
+use ratatui::{ backend::TestBackend, layout::Rect, Terminal };
+use std::fs;
+use std::path::PathBuf;
+
+// A fresh, empty directory for one test, e.g. pomonote-trash-<pid>-restore
+pub fn temp_dir(area: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pomonote-{}-{}-{}", area, std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Todos 1 to `count`, described "todo 1", "todo 2", ...
+pub fn state_with(count: u32) -> AppState {
+    AppState::new((1..=count).map(|id| Todo::new(id, format!("todo {}", id))).collect())
+}
+
+pub fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+// Feeds a key through the same path as the event loop
+pub fn press(state: &mut AppState, code: KeyCode) {
+    if let Some(command) = app::table_command(key(code), state) {
+        let actions = parse_command_for(&command, &state.todos);
+        app::apply_all(state, &command, actions.into_vec());
+        return;
+    }
+    let actions = app::handle_key_event(key(code), state);
+    if !matches!(code, KeyCode::Tab | KeyCode::BackTab) {
+        reduce(state, Action::CloseCompletion);
+    }
+    actions
+        .into_vec()
+        .into_iter()
+        .for_each(|a| reduce(state, a));
+}
+
+// Draws the UI at `size` and returns each line of the screen
+pub fn screen(state: &AppState, size: Rect) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(size.width, size.height)).unwrap();
+    terminal.draw(|f| display::ui(f, state)).unwrap();
+    let buffer = terminal.backend().buffer().clone();
+    (0..size.height)
+        .map(|y| (0..size.width).map(|x| buffer.get(x, y).symbol().to_string()).collect())
+        .collect()
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use common::{ press, screen };
+use crossterm::event::KeyCode;
+use pomonote::completion::{ self, Candidate, COMMANDS };
+use pomonote::models::todo::{ Todo, Trashed };
+use pomonote::reduce::{ parse_command, reduce, Action, AppState, Focus };
+use pomonote::utils::OneOrMany;
+use ratatui::layout::Rect;
+use std::fs;
+
+fn get_initial_state() -> AppState {
+    AppState::new(
+        vec![
+            Todo::new(1, "Write report +work".to_string()),
+            Todo::new(2, "Call the bank +home +Work".to_string()),
+            Todo::new(10, "Fix the sink +home".to_string())
+        ]
+    )
+}
+
+// Candidate texts for `input` with the cursor at its end
+fn texts(state: &AppState, input: &str) -> Vec<String> {
+    completion::complete(input, input.chars().count(), state)
+        .candidates
+        .into_iter()
+        .map(|c| c.text)
+        .collect()
+}
+
+// Typing any key closes the suggestions
+fn type_text(state: &mut AppState, text: &str) {
+    reduce(state, Action::CloseCompletion);
+    state.set_input(text.to_string());
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use super::*;
+
+    #[test]
+    fn test_every_command_parses() {
+        for (command, _) in COMMANDS {
+            let parsed = matches!(parse_command(command), OneOrMany::One(Action::SetOutput(ref s)) if s == "Invalid command");
+            assert!(!parsed, "{} is offered but not understood", command);
+        }
+    }
+
+    #[test]
+    fn test_commands_and_aliases() {
+        let state = get_initial_state();
+        assert_eq!(texts(&state, "r"), vec!["remove", "rm", "resume", "restore", "redo"]);
+        assert_eq!(texts(&state, "q"), vec!["quit"]);
+        assert!(texts(&state, "").contains(&"done".to_string()));
+        assert!(texts(&state, "nope").is_empty());
+        assert_eq!(texts(&state, "list s"), vec!["switch"]);
+    }
+
+    #[test]
+    fn test_ids_with_descriptions() {
+        let mut state = get_initial_state();
+        let completion = completion::complete("done 1", 6, &state);
+        assert_eq!(completion.start, 5);
+        assert_eq!(completion.candidates, vec![Candidate { text: "10".to_string(), detail: "Fix the sink +home".to_string() }]);
+        assert_eq!(texts(&state, "rm 2 "), vec!["1", "2", "10"]);
+        assert_eq!(texts(&state, "edit "), vec!["1", "2", "10"]);
+        assert!(texts(&state, "edit 1 ").is_empty());
+        assert!(texts(&state, "add ").is_empty());
+
+        state.trash.push(Trashed { todo: Todo::new(7, "Old idea".to_string()), deleted_at: 0 });
+        assert_eq!(texts(&state, "restore "), vec!["7"]);
+    }
+
+    #[test]
+    fn test_non_ascii_whitespace() {
+        let state = get_initial_state();
+        let completion = completion::complete("rm\u{a0}1", 4, &state);
+        assert_eq!(completion.start, 3);
+        assert_eq!(texts(&state, "rm\u{a0}1"), vec!["10"]);
+        assert_eq!(texts(&state, "é\u{3000}r").len(), 0);
+    }
+
+    #[test]
+    fn test_tags_anywhere() {
+        let state = get_initial_state();
+        let completion = completion::complete("add Pay rent +", 14, &state);
+        let tags: Vec<(String, String)> = completion.candidates
+            .into_iter()
+            .map(|c| (c.text, c.detail))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![("+home".to_string(), "2 todos".to_string()), ("+work".to_string(), "2 todos".to_string())]
+        );
+        assert_eq!(texts(&state, "done +h"), vec!["+home"]);
+    }
+
+    #[test]
+    fn test_list_names() {
+        let dir = common::temp_dir("completion", "lists");
+        fs::create_dir_all(dir.join("lists")).unwrap();
+        fs::write(dir.join("lists").join("garden.json"), "[]").unwrap();
+        fs::write(dir.join("lists").join("groceries.json"), "[]").unwrap();
+
+        let mut state = get_initial_state();
+        state.lists.data_dir = dir.clone();
+        assert_eq!(texts(&state, "list switch g"), vec!["global", "garden", "groceries"]);
+        assert_eq!(texts(&state, "list switch gr"), vec!["groceries"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_single_candidate_is_filled_in() {
+        let mut state = get_initial_state();
+        type_text(&mut state, "empty 3");
+        state.input_cursor = 2;
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "empty-trash pty 3");
+        assert_eq!(state.input_cursor, 12);
+        assert!(state.completion.is_none());
+
+        type_text(&mut state, "done 1");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "done 10 ");
+    }
+
+    #[test]
+    fn test_tab_cycles_through_candidates() {
+        let mut state = get_initial_state();
+        type_text(&mut state, "st");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "st");
+        assert_eq!(state.completion.as_ref().unwrap().candidates.len(), 3);
+
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "start");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "stop");
+        press(&mut state, KeyCode::BackTab);
+        press(&mut state, KeyCode::BackTab);
+        assert_eq!(state.input_buffer, "stats");
+
+        // Esc only closes the popup, and typing goes on from the candidate
+        press(&mut state, KeyCode::Esc);
+        assert!(state.completion.is_none());
+        assert!(!state.should_quit);
+        press(&mut state, KeyCode::Char('!'));
+        assert_eq!(state.input_buffer, "stats!");
+    }
+
+    #[test]
+    fn test_common_prefix_is_filled_in() {
+        let mut state = get_initial_state();
+        type_text(&mut state, "em");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "empty-trash ");
+
+        type_text(&mut state, "add +");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "add +");
+        type_text(&mut state, "list switch gl");
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.input_buffer, "list switch global ");
+    }
+
+    #[test]
+    fn test_tab_on_empty_input_switches_focus() {
+        let mut state = get_initial_state();
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.focus, Focus::Table);
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.focus, Focus::Input);
+    }
+
+    #[test]
+    fn test_popup_is_drawn_above_input() {
+        let mut state = get_initial_state();
+        type_text(&mut state, "r");
+        press(&mut state, KeyCode::Tab);
+
+        let lines = screen(&state, Rect::new(0, 0, 100, 40));
+        let input_row = lines
+            .iter()
+            .rposition(|line| line.contains("Input"))
+            .unwrap();
+        let alias_row = lines
+            .iter()
+            .position(|line| line.contains("rm") && line.contains("Alias of remove"))
+            .unwrap();
+        assert!(alias_row < input_row);
+        assert!(input_row - alias_row <= 8);
+    }
+}
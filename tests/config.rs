@@ -0,0 +1,93 @@
+use pomonote::config::Config;
+use pomonote::models::cycle::Cycle;
+use pomonote::utils::parse_duration;
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_matches_default_cycle() {
+        assert_eq!(Config::default().cycle(), Some(Cycle::default()));
+    }
+
+    #[test]
+    fn test_parse_partial_config() {
+        let config = Config::parse(r#"{"work": 50, "short_break": 10}"#).unwrap();
+        let cycle = config.cycle().unwrap();
+        assert_eq!(cycle.work, 3000);
+        assert_eq!(cycle.short_break, 600);
+        assert_eq!(cycle.long_break, 900);
+        assert_eq!(cycle.long_break_every, 4);
+    }
+
+    #[test]
+    fn test_parse_notify_config() {
+        let config = Config::parse(
+            r#"{"notify": {"bell": false, "command": "notify-send \"$POMONOTE_TITLE\""}}"#
+        ).unwrap();
+        assert!(!config.notify.bell);
+        assert!(config.notify.banner);
+        assert_eq!(config.notify.command.as_deref(), Some("notify-send \"$POMONOTE_TITLE\""));
+    }
+
+    #[test]
+    fn test_huge_phase_is_no_cycle() {
+        let config = Config::parse(r#"{"long_break": 999999999999999999}"#).unwrap();
+        assert_eq!(config.cycle(), None);
+    }
+
+    #[test]
+    fn test_zero_phase_is_no_cycle() {
+        for phase in ["work", "short_break", "long_break"] {
+            let config = Config::parse(&format!(r#"{{"{}": 0}}"#, phase)).unwrap();
+            assert_eq!(config.cycle(), None, "{} of 0 was accepted", phase);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        assert!(Config::parse(r#"{"work": "fifty"}"#).is_err());
+    }
+}
+
+#[cfg(test)]
+mod duration_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("50m"), Some(3000));
+        assert_eq!(parse_duration("90s"), Some(90));
+        assert_eq!(parse_duration("1h"), Some(3600));
+        assert_eq!(parse_duration("1h30m"), Some(5400));
+    }
+
+    #[test]
+    fn test_parse_duration_bare_number_is_minutes() {
+        assert_eq!(parse_duration("45"), Some(2700));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_zero() {
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("0h0s"), None);
+    }
+
+    #[test]
+    fn test_parse_duration_overflow() {
+        assert_eq!(parse_duration("999999999999999999"), None);
+        assert_eq!(parse_duration("999999999999999999m"), None);
+        assert_eq!(parse_duration("9999999999999999h"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+    }
+}
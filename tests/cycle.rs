@@ -0,0 +1,56 @@
+use pomonote::models::cycle::{ Cycle, Phase };
+use pomonote::models::timer::Timer;
+
+#[cfg(test)]
+mod cycle_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_cycle_is_classic_pomodoro() {
+        let cycle = Cycle::default();
+        assert_eq!(cycle.duration(Phase::Work), 1500);
+        assert_eq!(cycle.duration(Phase::ShortBreak), 300);
+        assert_eq!(cycle.duration(Phase::LongBreak), 900);
+        assert_eq!(cycle.long_break_every, 4);
+    }
+
+    #[test]
+    fn test_cycle_next_sequence() {
+        let cycle = Cycle::default();
+        assert_eq!(cycle.next(Phase::Work, 1), (Phase::ShortBreak, 1));
+        assert_eq!(cycle.next(Phase::ShortBreak, 1), (Phase::Work, 2));
+        assert_eq!(cycle.next(Phase::Work, 4), (Phase::LongBreak, 4));
+        assert_eq!(cycle.next(Phase::LongBreak, 4), (Phase::Work, 1));
+    }
+
+    #[test]
+    fn test_timer_advance_starts_next_phase() {
+        let cycle = Cycle { work: 0, ..Cycle::default() };
+        let mut timer = Timer::for_phase(Phase::Work, 1, &cycle);
+        timer.start();
+        assert!(timer.is_finished());
+
+        let next = timer.advance(&cycle);
+        assert_eq!(next, Phase::ShortBreak);
+        assert_eq!(timer.phase(), Phase::ShortBreak);
+        assert_eq!(timer.round(), 1);
+        assert!(!timer.is_finished());
+        assert!(timer.remaining_seconds() <= 300);
+    }
+
+    #[test]
+    fn test_timer_phase_label() {
+        let cycle = Cycle::default();
+        assert_eq!(Timer::new().phase_label(&cycle), "Work 1/4");
+        let timer = Timer::for_phase(Phase::LongBreak, 4, &cycle);
+        assert_eq!(timer.phase_label(&cycle), "Long Break");
+    }
+
+    #[test]
+    fn test_timer_deserializes_without_phase() {
+        let json = r#"{"duration":1500,"start_timestamp":null}"#;
+        let timer: Timer = serde_json::from_str(json).unwrap();
+        assert_eq!(timer.phase(), Phase::Work);
+        assert_eq!(timer.round(), 1);
+    }
+}
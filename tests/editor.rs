@@ -0,0 +1,153 @@
+mod common;
+
+use crossterm::event::{ KeyCode, KeyEvent, KeyModifiers };
+use pomonote::app;
+use pomonote::editor::{ self, CommandHistory, Edit, COMMAND_HISTORY_LIMIT };
+use pomonote::models::todo::Todo;
+use pomonote::reduce::{ reduce, Action, AppState, Effect };
+use pomonote::utils::OneOrMany;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str) -> PathBuf {
+    common::temp_dir("editor", name).join("command_history")
+}
+
+// Applies edits starting from `text` with the cursor at its end
+fn edited(text: &str, edits: &[Edit]) -> (String, usize) {
+    let mut text = text.to_string();
+    let mut cursor = text.chars().count();
+    for edit in edits {
+        cursor = editor::apply(*edit, &mut text, cursor);
+    }
+    (text, cursor)
+}
+
+fn key_action(code: KeyCode, modifiers: KeyModifiers) -> Option<Edit> {
+    match app::handle_key_event(KeyEvent::new(code, modifiers), &AppState::new(vec![])) {
+        OneOrMany::One(Action::EditInput(edit)) => Some(edit),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod editor_tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_delete_around_cursor() {
+        assert_eq!(edited("ad 1", &[Edit::Left, Edit::Left, Edit::Left, Edit::Insert('d')]), ("add 1".to_string(), 2));
+        assert_eq!(edited("abc", &[Edit::Home, Edit::Delete, Edit::End, Edit::Backspace]), ("b".to_string(), 1));
+        assert_eq!(edited("", &[Edit::Backspace, Edit::Left, Edit::Delete]), (String::new(), 0));
+        assert_eq!(edited("x", &[Edit::Right, Edit::Right]), ("x".to_string(), 1));
+    }
+
+    #[test]
+    fn test_counts_characters_not_bytes() {
+        assert_eq!(edited("café ☕", &[Edit::Left, Edit::Backspace]), ("café☕".to_string(), 4));
+        assert_eq!(edited("é", &[Edit::Home, Edit::Insert('ü')]), ("üé".to_string(), 1));
+    }
+
+    #[test]
+    fn test_word_motions() {
+        assert_eq!(edited("start 3  50m", &[Edit::WordLeft]).1, 9);
+        assert_eq!(edited("start 3  50m", &[Edit::WordLeft, Edit::WordLeft]).1, 6);
+        assert_eq!(edited("start 3  50m", &[Edit::Home, Edit::WordRight]).1, 5);
+        assert_eq!(edited("start 3  50m", &[Edit::Home, Edit::WordRight, Edit::WordRight]).1, 7);
+    }
+
+    #[test]
+    fn test_readline_deletes() {
+        assert_eq!(edited("add buy  milk  ", &[Edit::DeleteWordBack]), ("add buy  ".to_string(), 9));
+        assert_eq!(edited("add buy milk", &[Edit::WordLeft, Edit::DeleteToStart]), ("milk".to_string(), 0));
+        assert_eq!(edited("add buy milk", &[Edit::WordLeft, Edit::DeleteToEnd]), ("add buy ".to_string(), 8));
+    }
+
+    #[test]
+    fn test_keys() {
+        let ctrl = KeyModifiers::CONTROL;
+        assert_eq!(key_action(KeyCode::Char('a'), ctrl), Some(Edit::Home));
+        assert_eq!(key_action(KeyCode::Char('e'), ctrl), Some(Edit::End));
+        assert_eq!(key_action(KeyCode::Char('w'), ctrl), Some(Edit::DeleteWordBack));
+        assert_eq!(key_action(KeyCode::Char('u'), ctrl), Some(Edit::DeleteToStart));
+        assert_eq!(key_action(KeyCode::Char('k'), ctrl), Some(Edit::DeleteToEnd));
+        assert_eq!(key_action(KeyCode::Left, ctrl), Some(Edit::WordLeft));
+        assert_eq!(key_action(KeyCode::Char('f'), KeyModifiers::ALT), Some(Edit::WordRight));
+        assert_eq!(key_action(KeyCode::Home, KeyModifiers::NONE), Some(Edit::Home));
+        assert_eq!(key_action(KeyCode::Delete, KeyModifiers::NONE), Some(Edit::Delete));
+        assert_eq!(key_action(KeyCode::Char('q'), ctrl), None);
+        assert_eq!(key_action(KeyCode::Char('A'), KeyModifiers::SHIFT), Some(Edit::Insert('A')));
+    }
+
+    #[test]
+    fn test_history_browsing_keeps_draft() {
+        let mut history = CommandHistory::new(vec!["add a".to_string(), "done 1".to_string()]);
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older("sta"), Some("done 1"));
+        assert_eq!(history.older("ignored"), Some("add a"));
+        assert_eq!(history.older("ignored"), Some("add a"));
+        assert_eq!(history.newer(), Some("done 1"));
+        assert_eq!(history.newer(), Some("sta"));
+        assert_eq!(history.newer(), None);
+        assert_eq!(CommandHistory::default().older("x"), None);
+    }
+
+    #[test]
+    fn test_history_push_skips_repeats() {
+        let mut history = CommandHistory::default();
+        assert!(history.push("stats"));
+        assert!(!history.push(" stats "));
+        assert!(!history.push("   "));
+        assert!(history.push("undo"));
+        assert_eq!(history.entries(), ["stats", "undo"]);
+        for i in 0..COMMAND_HISTORY_LIMIT {
+            history.push(&format!("add {}", i));
+        }
+        assert_eq!(history.entries().len(), COMMAND_HISTORY_LIMIT);
+        assert_eq!(history.entries()[0], "add 0");
+    }
+
+    #[test]
+    fn test_history_file() {
+        let path = temp_file("file");
+        assert!(CommandHistory::load(&path).entries().is_empty());
+        CommandHistory::append(&path, "add a").unwrap();
+        CommandHistory::append(&path, "rm 1").unwrap();
+        assert_eq!(CommandHistory::load(&path).entries(), ["add a", "rm 1"]);
+
+        let lines: Vec<String> = (0..COMMAND_HISTORY_LIMIT + 10).map(|i| format!("add {}", i)).collect();
+        fs::write(&path, lines.join("\n")).unwrap();
+        let history = CommandHistory::load(&path);
+        assert_eq!(history.entries().len(), COMMAND_HISTORY_LIMIT);
+        assert_eq!(history.entries()[0], "add 10");
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), COMMAND_HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn test_reducer_edits_input() {
+        let mut state = AppState::new(vec![Todo::new(1, "buy milk".to_string())]);
+        reduce(&mut state, Action::UpdateInput("rm 1".to_string()));
+        assert_eq!(state.input_cursor, 4);
+        reduce(&mut state, Action::EditInput(Edit::Home));
+        reduce(&mut state, Action::EditInput(Edit::DeleteToEnd));
+        assert!(state.input_buffer.is_empty());
+
+        reduce(&mut state, Action::BeginEdit(1));
+        assert_eq!(state.input_cursor, state.input_buffer.chars().count());
+    }
+
+    #[test]
+    fn test_remembered_commands_are_saved_and_recalled() {
+        let mut state = AppState::new(vec![]);
+        reduce(&mut state, Action::RememberCommand("add tea".to_string()));
+        reduce(&mut state, Action::RememberCommand("add tea".to_string()));
+        assert_eq!(state.effects.len(), 1);
+        assert!(matches!(&state.effects[0], Effect::SaveCommand(c) if c == "add tea"));
+
+        reduce(&mut state, Action::UpdateInput("dra".to_string()));
+        reduce(&mut state, Action::RecallOlder);
+        assert_eq!((state.input_buffer.as_str(), state.input_cursor), ("add tea", 7));
+        reduce(&mut state, Action::RecallNewer);
+        assert_eq!(state.input_buffer, "dra");
+    }
+}
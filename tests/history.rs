@@ -0,0 +1,76 @@
+mod common;
+
+use pomonote::models::history::{ HistoryEntry, Outcome };
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+fn temp_file(name: &str) -> PathBuf {
+    common::temp_dir("history", name).join(name)
+}
+
+fn entry(todo_id: u32, start: u64, outcome: Outcome) -> HistoryEntry {
+    HistoryEntry {
+        todo_id,
+        description: format!("todo {}", todo_id),
+        start,
+        end: start + 1500,
+        duration: 1500,
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+
+    #[test]
+    fn test_path_for_sits_next_to_todos() {
+        assert_eq!(
+            HistoryEntry::path_for(Path::new("/data/todos.json")),
+            PathBuf::from("/data/todos.history.jsonl")
+        );
+        assert_eq!(
+            HistoryEntry::path_for(Path::new("/repo/.pomonote.json")),
+            PathBuf::from("/repo/.pomonote.history.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_append_and_load_round_trip() {
+        let path = temp_file("round_trip.jsonl");
+        let first = entry(1, 100, Outcome::Finished);
+        let second = entry(2, 200, Outcome::Interrupted);
+        HistoryEntry::append(&path, std::slice::from_ref(&first)).unwrap();
+        HistoryEntry::append(&path, std::slice::from_ref(&second)).unwrap();
+
+        assert_eq!(HistoryEntry::load_all(&path), vec![first, second]);
+    }
+
+    #[test]
+    fn test_load_skips_corrupt_lines() {
+        let path = temp_file("corrupt.jsonl");
+        HistoryEntry::append(&path, &[entry(1, 100, Outcome::Finished)]).unwrap();
+        let mut data = fs::read_to_string(&path).unwrap();
+        data.push_str("{not json\n");
+        fs::write(&path, data).unwrap();
+        HistoryEntry::append(&path, &[entry(2, 200, Outcome::Finished)]).unwrap();
+
+        assert_eq!(HistoryEntry::load_all(&path).len(), 2);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(HistoryEntry::load_all(&temp_file("missing.jsonl")).is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let entries = vec![
+            entry(1, 100, Outcome::Finished),
+            entry(2, 200, Outcome::Finished),
+            entry(1, 300, Outcome::Interrupted)
+        ];
+        assert_eq!(HistoryEntry::for_todo(&entries, 1).len(), 2);
+        assert_eq!(HistoryEntry::between(&entries, 150, 300).len(), 1);
+    }
+}
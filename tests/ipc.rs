@@ -0,0 +1,156 @@
+mod common;
+
+use pomonote::app;
+use pomonote::cli::Command;
+use pomonote::ipc::{ self, Server };
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::reduce::{ Action, AppState };
+use serde_json::Value;
+use std::fs;
+use std::io::{ Read, Write };
+use std::os::unix::net::{ UnixListener, UnixStream };
+use std::path::PathBuf;
+use std::thread;
+use std::time::{ Duration, Instant };
+
+fn temp_file(name: &str) -> PathBuf {
+    common::temp_dir("ipc", name).join("todos.json")
+}
+
+// Answers requests until one has been served, like one pass of the TUI loop
+fn serve_one(server: &mut Server, state: &mut AppState) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        let connections = server.poll();
+        if !connections.is_empty() {
+            for connection in connections {
+                let report = app::handle_request(state, &connection.request);
+                connection.reply(&report);
+            }
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("no request arrived");
+}
+
+#[cfg(test)]
+mod ipc_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_json_and_text() {
+        let actions = ipc::parse_request(r#"{"command":"start","ids":[3],"duration":600}"#, &[]).unwrap();
+        assert!(matches!(actions[..], [Action::StartTodoFor(3, 600)]));
+
+        let actions = ipc::parse_request(r#"{"command":"complete","ids":[1,2]}"#, &[]).unwrap();
+        assert!(matches!(actions[..], [Action::CompleteTodo(1), Action::CompleteTodo(2)]));
+
+        let actions = ipc::parse_request("stop 4\n", &[]).unwrap();
+        assert!(matches!(actions[..], [Action::StopTodo(4)]));
+
+        assert!(ipc::parse_request(r#"{"command":"launch"}"#, &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_request_only_takes_cli_commands() {
+        let todos = [Todo::new(1, "write".to_string())];
+        assert!(ipc::parse_request("done 1", &todos).is_ok());
+        assert_eq!(ipc::parse_request("quit", &todos).err().unwrap(), "❌ 'quit' can't be sent to a running pomonote.");
+        for line in ["quit", "undo", "redo", "list switch other", "list new other", "list", "stats", "edit 1"] {
+            assert!(ipc::parse_request(line, &todos).is_err(), "{} was accepted", line);
+        }
+    }
+
+    #[test]
+    fn test_command_round_trip() {
+        let command = Command::Add { text: vec!["buy".to_string(), "2".to_string(), "eggs".to_string()] };
+        let json = serde_json::to_string(&command).unwrap();
+        let actions = ipc::parse_request(&json, &[]).unwrap();
+        assert!(matches!(&actions[..], [Action::AddTodo(text)] if text == "buy 2 eggs"));
+    }
+
+    #[test]
+    fn test_socket_path_is_per_file() {
+        assert_eq!(ipc::socket_path(&temp_file("a")), ipc::socket_path(&temp_file("a")));
+        assert_ne!(ipc::socket_path(&temp_file("a")), ipc::socket_path(&temp_file("b")));
+    }
+
+    #[test]
+    fn test_send_without_server() {
+        assert_eq!(ipc::send(&temp_file("nobody"), "stop 1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_send_reaches_server() {
+        let file = temp_file("serve");
+        let mut server = Server::bind(&file).unwrap();
+        let mut state = AppState::new(vec![Todo::new(1, "write".to_string())]);
+
+        let client_file = file.clone();
+        let client = thread::spawn(move || ipc::send(&client_file, r#"{"command":"start","ids":[1]}"#));
+        serve_one(&mut server, &mut state);
+
+        let reply: Value = serde_json::from_str(&client.join().unwrap().unwrap().unwrap()).unwrap();
+        assert_eq!(reply["version"], 1);
+        assert_eq!(reply["ok"], true);
+        assert_eq!(reply["todos"][0]["status"], "in_progress");
+        assert_eq!(state.todos[0].status, TodoStatus::InProgress);
+    }
+
+    #[test]
+    fn test_server_reports_failures() {
+        let file = temp_file("fail");
+        let mut server = Server::bind(&file).unwrap();
+        let mut state = AppState::new(vec![]);
+
+        let client_file = file.clone();
+        let client = thread::spawn(move || ipc::send(&client_file, "done 9"));
+        serve_one(&mut server, &mut state);
+
+        let reply: Value = serde_json::from_str(&client.join().unwrap().unwrap().unwrap()).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert!(reply["results"][0]["message"].as_str().unwrap().contains("not found"));
+    }
+
+    #[test]
+    fn test_bind_refuses_second_instance() {
+        let file = temp_file("twice");
+        let _server = Server::bind(&file).unwrap();
+        assert!(Server::bind(&file).is_err());
+    }
+
+    #[test]
+    fn test_bind_replaces_stale_socket() {
+        let file = temp_file("stale");
+        let path = ipc::socket_path(&file);
+        let _ = fs::remove_file(&path);
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let server = Server::bind(&file).unwrap();
+        assert_eq!(server.path(), path);
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_poll_does_not_wait_for_slow_clients() {
+        let file = temp_file("slow");
+        let mut server = Server::bind(&file).unwrap();
+        let mut state = AppState::new(vec![Todo::new(1, "write".to_string())]);
+
+        let mut client = UnixStream::connect(ipc::socket_path(&file)).unwrap();
+        client.write_all(b"start").unwrap();
+        let started = Instant::now();
+        assert!(server.poll().is_empty());
+        assert!(started.elapsed() < Duration::from_millis(100));
+
+        client.write_all(b" 1\n").unwrap();
+        serve_one(&mut server, &mut state);
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).unwrap();
+        assert!(reply.contains("\"ok\":true"));
+        assert_eq!(state.todos[0].status, TodoStatus::InProgress);
+    }
+}
@@ -29,6 +29,7 @@ mod integration_tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_multiple_todos_workflow() {
         let mut todos = vec![
             Todo::new(1, "Task 1".to_string()),
@@ -0,0 +1,120 @@
+mod common;
+
+use pomonote::lists::{ self, Lists, GLOBAL, PROJECT };
+use pomonote::models::todo::Todo;
+use pomonote::reduce::AppState;
+use pomonote::storage;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+fn temp_dir(name: &str) -> PathBuf {
+    common::temp_dir("lists", name)
+}
+
+fn state_in(dir: &Path) -> AppState {
+    let lists = Lists {
+        data_dir: dir.to_path_buf(),
+        project: None,
+        current: GLOBAL.to_string(),
+    };
+    let mut state = AppState::new(vec![Todo::new(1, "global todo".to_string())]);
+    state.file = lists.path(GLOBAL).unwrap();
+    state.lists = lists;
+    state
+}
+
+#[cfg(test)]
+mod lists_tests {
+    use super::*;
+
+    #[test]
+    fn test_new_list_saves_current_and_switches() {
+        let dir = temp_dir("new");
+        let mut state = state_in(&dir);
+
+        lists::switch(&mut state, "backend", true);
+        assert_eq!(state.lists.current, "backend");
+        assert!(state.todos.is_empty());
+        assert_eq!(state.file, dir.join("lists/backend.json"));
+        assert_eq!(Todo::load_all(&dir.join("todos.json")).unwrap()[0].description, "global todo");
+        assert_eq!(state.lists.names(), vec!["global", "backend"]);
+
+        lists::switch(&mut state, GLOBAL, false);
+        assert_eq!(state.todos.len(), 1);
+    }
+
+    #[test]
+    fn test_switch_to_missing_list() {
+        let dir = temp_dir("missing");
+        let mut state = state_in(&dir);
+        lists::switch(&mut state, "nope", false);
+        assert_eq!(state.lists.current, GLOBAL);
+        assert!(state.output_buffer.contains("not found"));
+    }
+
+    #[test]
+    fn test_switch_stays_inside_lists_dir() {
+        let dir = temp_dir("escape");
+        let outside = dir.join("outside.json");
+        fs::write(&outside, "not a list").unwrap();
+        let mut state = state_in(&dir.join("data"));
+        lists::switch(&mut state, "../../outside", false);
+        assert_eq!(state.lists.current, GLOBAL);
+        assert!(state.output_buffer.contains("Invalid list name"));
+        assert_eq!(fs::read_to_string(&outside).unwrap(), "not a list");
+        assert_eq!(state.lists.path("../outside"), None);
+        assert_eq!(state.lists.path("a/b"), None);
+    }
+
+    #[test]
+    fn test_new_list_refuses_existing() {
+        let dir = temp_dir("existing");
+        let mut state = state_in(&dir);
+        lists::switch(&mut state, "backend", true);
+        lists::switch(&mut state, "backend", true);
+        assert!(state.output_buffer.contains("already exists"));
+    }
+
+    #[test]
+    fn test_project_list_needs_discovery() {
+        let dir = temp_dir("no-project");
+        let mut state = state_in(&dir);
+        lists::switch(&mut state, PROJECT, false);
+        assert_eq!(state.lists.current, GLOBAL);
+    }
+
+    #[test]
+    fn test_init_project_at_git_root() {
+        let dir = temp_dir("init");
+        let repo = dir.join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("src/deep")).unwrap();
+        let mut state = state_in(&dir);
+
+        lists::init_project(&mut state, &repo.join("src/deep"));
+        assert_eq!(state.lists.current, PROJECT);
+        assert_eq!(state.file, repo.join(".pomonote.json"));
+        assert_eq!(storage::discover_project(&repo.join("src/deep")), Some(repo.join(".pomonote.json")));
+    }
+
+    #[test]
+    fn test_name_for() {
+        let dir = temp_dir("names");
+        let lists = Lists {
+            data_dir: dir.clone(),
+            project: Some(PathBuf::from("/repo/.pomonote.json")),
+            current: GLOBAL.to_string(),
+        };
+        assert_eq!(lists.name_for(&dir.join("todos.json")), GLOBAL);
+        assert_eq!(lists.name_for(&PathBuf::from("/repo/.pomonote.json")), PROJECT);
+        assert_eq!(lists.name_for(&PathBuf::from("/tmp/work.json")), "work");
+    }
+
+    #[test]
+    fn test_valid_names() {
+        assert!(lists::is_valid_name("backend-2"));
+        assert!(!lists::is_valid_name("../etc"));
+        assert!(!lists::is_valid_name(""));
+        assert!(!lists::is_valid_name(PROJECT));
+    }
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{ press, state_with };
+use crossterm::event::{ KeyCode, KeyModifiers, MouseEvent, MouseEventKind };
+use pomonote::app::{ self, Clicks };
+use pomonote::display;
+use pomonote::log::{ OutputLog, OUTPUT_LOG_LIMIT };
+use pomonote::reduce::{ parse_command_for, reduce, AppState, Focus };
+use pomonote::utils::{ clock, clock_at };
+use ratatui::layout::Rect;
+
+const SIZE: Rect = Rect { x: 0, y: 0, width: 80, height: 30 };
+
+// Scrolls the table and the log like the event loop does before drawing
+fn screen(state: &mut AppState) -> Vec<String> {
+    let areas = display::areas(SIZE, state);
+    state.table_offset = display::fit_offset(state, display::rows_height(areas.table));
+    state.log.fit(display::log_height(areas.output));
+    common::screen(state, SIZE)
+}
+
+#[cfg(test)]
+mod log_tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock_at(0, 0), "00:00:00");
+        assert_eq!(clock_at(86_400 * 3 + 13 * 3600 + 5 * 60 + 9, 0), "13:05:09");
+        assert_eq!(clock_at(3600, 2 * 3600), "03:00:00");
+        assert_eq!(clock_at(3600, -5 * 3600), "20:00:00");
+        assert_eq!(clock_at(0, 5 * 3600 + 30 * 60), "05:30:00");
+    }
+
+    #[test]
+    fn test_push_keeps_messages_with_their_time() {
+        let mut log = OutputLog::default();
+        log.push(3600, "✅ Todo 1 added.\n");
+        log.push(3660, "   ");
+        log.push(3720, "first\nsecond");
+        assert_eq!(log.entries().len(), 2);
+        assert_eq!(
+            log.lines(),
+            vec![
+                (Some(clock(3600)), "✅ Todo 1 added."),
+                (Some(clock(3720)), "first"),
+                (None, "second")
+            ]
+        );
+        assert_eq!(log.line_count(), 3);
+    }
+
+    #[test]
+    fn test_push_drops_the_oldest_past_the_limit() {
+        let mut log = OutputLog::default();
+        for i in 0..OUTPUT_LOG_LIMIT + 5 {
+            log.push(0, &format!("message {}", i));
+        }
+        assert_eq!(log.entries().len(), OUTPUT_LOG_LIMIT);
+        assert_eq!(log.entries()[0].text, "message 5");
+    }
+
+    #[test]
+    fn test_scroll_stays_in_range_and_follows_new_messages() {
+        let mut log = OutputLog::default();
+        for i in 0..10 {
+            log.push(0, &format!("message {}", i));
+        }
+        log.scroll_by(-3);
+        assert_eq!(log.scroll, 0);
+        log.scroll_by(100);
+        log.fit(4);
+        assert_eq!(log.scroll, 6);
+        log.push(0, "new");
+        assert_eq!(log.scroll, 0);
+    }
+
+    #[test]
+    fn test_commands_log_every_message() {
+        let mut state = state_with(3);
+        let actions = parse_command_for("done 1 2", &state.todos);
+        app::apply_all(&mut state, "done 1 2", actions.into_vec());
+        app::log_output(&mut state);
+        assert!(state.output_buffer.is_empty());
+        assert_eq!(state.log.entries().len(), 1);
+        let lines: Vec<&str> = state.log
+            .lines()
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains('1') && lines[1].contains('2'));
+    }
+
+    #[test]
+    fn test_page_keys() {
+        let mut state = state_with(60);
+        press(&mut state, KeyCode::PageUp);
+        assert_eq!(state.log.scroll, 3);
+        press(&mut state, KeyCode::PageDown);
+        assert_eq!(state.log.scroll, 0);
+
+        state.focus = Focus::Table;
+        press(&mut state, KeyCode::PageDown);
+        press(&mut state, KeyCode::PageDown);
+        assert_eq!(state.selected_todo().unwrap().id, 21);
+        press(&mut state, KeyCode::PageUp);
+        assert_eq!(state.selected_todo().unwrap().id, 11);
+        press(&mut state, KeyCode::Char('G'));
+        assert_eq!(state.selected_todo().unwrap().id, 60);
+        press(&mut state, KeyCode::Home);
+        assert_eq!(state.selected_todo().unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_wheel_over_output_scrolls_the_log() {
+        let mut state = state_with(1);
+        let output = display::areas(SIZE, &state).output;
+        let mut clicks = Clicks::default();
+        let wheel = |kind| MouseEvent { kind, column: output.x + 2, row: output.y + 2, modifiers: KeyModifiers::NONE };
+        let input = app::handle_mouse_event(wheel(MouseEventKind::ScrollUp), &state, SIZE, &mut clicks);
+        reduce(&mut state, input.action);
+        assert_eq!(state.log.scroll, 1);
+        assert_eq!(state.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn test_long_list_scrolls_to_the_end_with_a_scrollbar() {
+        let mut state = state_with(60);
+        state.focus = Focus::Table;
+        let lines = screen(&mut state);
+        let right = |line: &String| line.chars().last().unwrap();
+        assert!(lines.iter().any(|line| right(line) == '█'));
+        assert!(!lines.iter().any(|line| line.contains("todo 60")));
+
+        press(&mut state, KeyCode::End);
+        let lines = screen(&mut state);
+        assert!(lines.iter().any(|line| line.contains("todo 60")));
+        assert!(!lines.iter().any(|line| line.contains("todo 1 ")));
+    }
+
+    #[test]
+    fn test_output_shows_newest_lines_with_times() {
+        let mut state = state_with(1);
+        for i in 0..20 {
+            state.log.push(3600 + i, &format!("message {}", i));
+        }
+        let lines = screen(&mut state);
+        assert!(lines.iter().any(|line| line.contains(&format!("{} message 19", clock(3619)))));
+        assert!(!lines.iter().any(|line| line.contains("message 10 ")));
+
+        state.log.scroll_by(100);
+        let lines = screen(&mut state);
+        assert!(lines.iter().any(|line| line.contains(&format!("{} message 0", clock(3600)))));
+        assert!(lines.iter().any(|line| line.contains("lines back")));
+    }
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{ screen, state_with };
+use crossterm::event::{ KeyModifiers, MouseButton, MouseEvent, MouseEventKind };
+use pomonote::app::{ self, Clicks };
+use pomonote::display::{ self, TableHit };
+use pomonote::notify::Banner;
+use pomonote::reduce::{ reduce, Action, AppState, Focus, View };
+use ratatui::layout::Rect;
+
+const SIZE: Rect = Rect { x: 0, y: 0, width: 100, height: 40 };
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+}
+
+fn click(column: u16, row: u16) -> MouseEvent {
+    mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+}
+
+// Where a todo's text is drawn, found by looking at the rendered screen
+fn find(state: &AppState, text: &str) -> (u16, u16) {
+    screen(state, SIZE)
+        .iter()
+        .enumerate()
+        .find_map(|(y, line)| line.find(text).map(|x| (line[..x].chars().count() as u16, y as u16)))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod mouse_tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_offset_follows_cursor() {
+        let mut state = state_with(20);
+        state.selected = 10;
+        assert_eq!(display::fit_offset(&state, 5), 6);
+        state.table_offset = 6;
+        state.selected = 8;
+        assert_eq!(display::fit_offset(&state, 5), 6);
+        state.selected = 2;
+        assert_eq!(display::fit_offset(&state, 5), 2);
+        // No empty space is left under the last row
+        state.table_offset = 19;
+        state.selected = 19;
+        assert_eq!(display::fit_offset(&state, 5), 15);
+    }
+
+    #[test]
+    fn test_hits_match_what_is_drawn() {
+        let state = state_with(3);
+        let table = display::areas(SIZE, &state).table;
+        let (x, y) = find(&state, "todo 2");
+        assert_eq!(display::table_hit(&state, table, x, y), Some(TableHit::Row(1)));
+        let (x, y) = find(&state, "Pending");
+        assert_eq!(display::table_hit(&state, table, x, y), Some(TableHit::Status(0)));
+        assert_eq!(display::table_hit(&state, table, x, table.y + 1), None);
+    }
+
+    #[test]
+    fn test_hits_follow_scrolling() {
+        let mut state = state_with(60);
+        state.selected = 50;
+        state.table_offset = display::fit_offset(&state, display::rows_height(display::areas(SIZE, &state).table));
+        let table = display::areas(SIZE, &state).table;
+        let (x, y) = find(&state, "todo 40 ");
+        assert_eq!(display::table_hit(&state, table, x, y), Some(TableHit::Row(39)));
+    }
+
+    #[test]
+    fn test_click_selects_and_double_click_toggles() {
+        let mut state = state_with(3);
+        let mut clicks = Clicks::default();
+        let (x, y) = find(&state, "todo 3");
+
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut clicks);
+        assert!(matches!(input.action, Action::SelectRow(2)));
+        assert_eq!(input.command, None);
+        reduce(&mut state, input.action);
+        assert_eq!(state.focus, Focus::Table);
+
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut clicks);
+        assert_eq!(input.command, Some("3".to_string()));
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut clicks);
+        assert_eq!(input.command, None);
+    }
+
+    #[test]
+    fn test_click_under_banner_hits_row_as_drawn() {
+        let mut state = state_with(3);
+        state.banner = Some(Banner::new("Break over".to_string()));
+        let (x, y) = find(&state, "todo 3");
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut Clicks::default());
+        assert!(matches!(input.action, Action::SelectRow(2)));
+    }
+
+    #[test]
+    fn test_status_click_toggles() {
+        let state = state_with(3);
+        let (x, y) = find(&state, "Pending");
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut Clicks::default());
+        assert!(matches!(input.action, Action::SelectRow(0)));
+        assert_eq!(input.command, Some("1".to_string()));
+    }
+
+    #[test]
+    fn test_wheel_and_input_focus() {
+        let mut state = state_with(10);
+        state.focus = Focus::Table;
+        let table = display::areas(SIZE, &state).table;
+        let input = app::handle_mouse_event(mouse(MouseEventKind::ScrollDown, 5, table.y + 5), &state, SIZE, &mut Clicks::default());
+        reduce(&mut state, input.action);
+        assert_eq!((state.table_offset, state.selected), (1, 1));
+        reduce(&mut state, Action::ScrollTable(-3));
+        assert_eq!((state.table_offset, state.selected), (0, 0));
+
+        let field = display::areas(SIZE, &state).input;
+        let input = app::handle_mouse_event(click(field.x + 2, field.y + 1), &state, SIZE, &mut Clicks::default());
+        reduce(&mut state, input.action);
+        assert_eq!(state.focus, Focus::Input);
+    }
+
+    #[test]
+    fn test_table_ignores_mouse_in_stats_view() {
+        let mut state = state_with(3);
+        let (x, y) = find(&state, "todo 1");
+        state.view = View::Stats;
+        let input = app::handle_mouse_event(click(x, y), &state, SIZE, &mut Clicks::default());
+        assert!(matches!(input.action, Action::NoOp));
+    }
+}
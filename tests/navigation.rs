@@ -0,0 +1,131 @@
+mod common;
+
+use common::{ key, press, state_with };
+use crossterm::event::KeyCode;
+use pomonote::app;
+use pomonote::models::todo::TodoStatus;
+use pomonote::notify::Banner;
+use pomonote::reduce::{ reduce, Action, Focus, View };
+use pomonote::utils::OneOrMany;
+
+#[cfg(test)]
+mod navigation_tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_stays_in_the_list() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::SelectPrevious);
+        assert_eq!(state.selected_index(), Some(0));
+        for _ in 0..5 {
+            reduce(&mut state, Action::SelectNext);
+        }
+        assert_eq!(state.selected_index(), Some(2));
+
+        state.todos.truncate(1);
+        assert_eq!(state.selected_todo().unwrap().id, 1);
+        state.todos.clear();
+        assert_eq!(state.selected_index(), None);
+        reduce(&mut state, Action::SelectNext);
+        assert_eq!(state.selected_index(), None);
+    }
+
+    #[test]
+    fn test_letters_type_until_table_is_focused() {
+        let mut state = state_with(3);
+        press(&mut state, KeyCode::Char('j'));
+        assert_eq!(state.input_buffer, "j");
+        assert_eq!(state.selected_index(), Some(0));
+
+        // Tab completes what's typed, Shift-Tab always switches
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.focus, Focus::Input);
+        press(&mut state, KeyCode::BackTab);
+        assert_eq!(state.focus, Focus::Table);
+        press(&mut state, KeyCode::Char('j'));
+        press(&mut state, KeyCode::Char('j'));
+        press(&mut state, KeyCode::Char('k'));
+        assert_eq!(state.selected_todo().unwrap().id, 2);
+        assert_eq!(state.input_buffer, "j");
+
+        press(&mut state, KeyCode::Esc);
+        assert_eq!(state.focus, Focus::Input);
+        assert!(!state.should_quit);
+    }
+
+    #[test]
+    fn test_arrows_move_in_the_table_and_recall_in_the_input() {
+        let mut state = state_with(3);
+        state.focus = Focus::Table;
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Up);
+        assert_eq!(state.selected_index(), Some(1));
+
+        state.focus = Focus::Input;
+        reduce(&mut state, Action::RememberCommand("stats".to_string()));
+        press(&mut state, KeyCode::Up);
+        assert_eq!(state.input_buffer, "stats");
+        assert_eq!(state.selected_index(), Some(1));
+    }
+
+    #[test]
+    fn test_keys_act_on_selected_todo() {
+        let mut state = state_with(3);
+        state.focus = Focus::Table;
+        state.selected = 1;
+
+        press(&mut state, KeyCode::Char('s'));
+        assert_eq!(state.todos[1].status, TodoStatus::InProgress);
+        press(&mut state, KeyCode::Char('d'));
+        assert_eq!(state.todos[1].status, TodoStatus::Completed);
+        press(&mut state, KeyCode::Char(' '));
+        assert_eq!(state.todos[1].status, TodoStatus::Pending);
+
+        press(&mut state, KeyCode::Char('x'));
+        assert_eq!(state.todos.len(), 2);
+        assert_eq!(state.trash[0].todo.id, 2);
+        assert_eq!(state.selected_todo().unwrap().id, 3);
+
+        reduce(&mut state, Action::Undo);
+        assert_eq!(state.todos.len(), 3);
+        assert!(state.output_buffer.contains("rm 2"));
+    }
+
+    #[test]
+    fn test_edit_key_moves_to_input() {
+        let mut state = state_with(3);
+        state.focus = Focus::Table;
+        press(&mut state, KeyCode::Char('e'));
+        assert_eq!(state.focus, Focus::Input);
+        assert_eq!(state.input_buffer, "edit 1 todo 1");
+    }
+
+    #[test]
+    fn test_table_keys_need_focus_and_todo_view() {
+        let mut state = state_with(3);
+        assert_eq!(app::table_command(key(KeyCode::Char('s')), &state), None);
+        state.focus = Focus::Table;
+        assert_eq!(app::table_command(key(KeyCode::Char('s')), &state), Some("start 1".to_string()));
+        state.view = View::Stats;
+        assert_eq!(app::table_command(key(KeyCode::Char('s')), &state), None);
+        state.view = View::Todos;
+        state.todos.clear();
+        assert_eq!(app::table_command(key(KeyCode::Char('s')), &state), None);
+        assert!(matches!(app::handle_key_event(key(KeyCode::Char('q')), &state), OneOrMany::One(Action::NoOp)));
+    }
+
+    #[test]
+    fn test_key_after_banner_still_counts() {
+        let mut state = state_with(3);
+        state.banner = Some(Banner::new("Break over".to_string()));
+        assert!(!app::dismiss_banner(&mut state, key(KeyCode::Char('a'))));
+        assert!(state.banner.is_none());
+        assert!(!app::dismiss_banner(&mut state, key(KeyCode::Esc)));
+
+        state.banner = Some(Banner::new("Break over".to_string()));
+        assert!(app::dismiss_banner(&mut state, key(KeyCode::Esc)));
+        assert!(state.banner.is_none());
+        assert!(!state.should_quit);
+    }
+}
@@ -0,0 +1,74 @@
+use pomonote::models::history::{ HistoryEntry, Outcome };
+use pomonote::models::timer::Timer;
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::output::{ CommandResult, Report, StatsJson, TodoJson, JSON_VERSION };
+use pomonote::stats::Stats;
+use serde_json::{ json, Value };
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+
+    #[test]
+    fn test_todo_without_timer() {
+        let todo = Todo::new(3, "write docs".to_string());
+        let value = serde_json::to_value(TodoJson::from(&todo)).unwrap();
+        assert_eq!(value, json!({ "id": 3, "description": "write docs", "status": "pending", "timer": null }));
+    }
+
+    #[test]
+    fn test_todo_with_running_timer() {
+        let mut todo = Todo::new(1, "focus".to_string());
+        todo.status = TodoStatus::InProgress;
+        let mut timer = Timer::new();
+        timer.start();
+        todo.timer = Some(timer);
+
+        let value = serde_json::to_value(TodoJson::from(&todo)).unwrap();
+        assert_eq!(value["status"], "in_progress");
+        assert_eq!(value["timer"]["phase"], "work");
+        assert_eq!(value["timer"]["round"], 1);
+        assert_eq!(value["timer"]["duration"], 1500);
+        assert_eq!(value["timer"]["paused"], false);
+        assert!((1499..=1500).contains(&value["timer"]["remaining_seconds"].as_u64().unwrap()));
+        assert!(value["timer"]["started_at"].is_u64());
+    }
+
+    #[test]
+    fn test_report_shape() {
+        let report = Report {
+            version: JSON_VERSION,
+            list: "global".to_string(),
+            ok: false,
+            warnings: vec![],
+            results: vec![
+                CommandResult::success("✅ Added".to_string()),
+                CommandResult::error("❌ Todo with ID 9 not found".to_string())
+            ],
+            todos: vec![],
+            stats: None,
+        };
+        let value: Value = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(value["version"], 1);
+        assert_eq!(value["results"][0], json!({ "ok": true, "message": "✅ Added" }));
+        assert_eq!(value["results"][1]["ok"], false);
+        assert!(value.get("stats").is_none());
+    }
+
+    #[test]
+    fn test_stats_per_todo_objects() {
+        let entry = HistoryEntry {
+            todo_id: 2,
+            description: "review".to_string(),
+            start: 1_000_000,
+            end: 1_001_500,
+            duration: 1500,
+            outcome: Outcome::Finished,
+        };
+
+        let stats: StatsJson = Stats::from_history(&[entry], 1_001_500).into();
+        let value = serde_json::to_value(stats).unwrap();
+        assert_eq!(value["total"], 1);
+        assert_eq!(value["per_todo"][0], json!({ "id": 2, "description": "review", "seconds": 1500 }));
+    }
+}
@@ -1,18 +1,19 @@
-use pomonote::reduce::{ reduce, parse_command, Action, AppState };
+use pomonote::reduce::{ reduce, parse_command, parse_command_for, Action, AppState, Effect, View };
+use pomonote::models::cycle::{ Cycle, Phase };
+use pomonote::models::history::Outcome;
+use pomonote::models::timer::Timer;
 use pomonote::models::todo::{ Todo, TodoStatus };
 use pomonote::utils::OneOrMany;
+use std::time::{ SystemTime, UNIX_EPOCH };
 
 fn get_initial_state() -> AppState {
-    AppState {
-        todos: vec![
+    AppState::new(
+        vec![
             Todo::new(1, "todo 1".to_string()),
             Todo::new(2, "todo 2".to_string()),
             Todo::new(3, "todo 3".to_string())
-        ],
-        input_buffer: String::new(),
-        output_buffer: String::new(),
-        should_quit: false,
-    }
+        ]
+    )
 }
 
 #[test]
@@ -31,6 +32,56 @@ fn test_reduce_add_todo() {
     assert_eq!(state.todos.last().unwrap().description, todo_text);
 }
 
+#[test]
+fn test_reduce_edit_todo() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::EditTodo(2, "renamed".to_string()));
+    assert_eq!(state.todos[1].description, "renamed");
+    assert!(state.dirty);
+    assert!(!state.failed);
+
+    reduce(&mut state, Action::EditTodo(9, "nope".to_string()));
+    assert!(state.failed);
+    assert!(state.output_buffer.contains("not found"));
+}
+
+#[test]
+fn test_reduce_edit_todo_rejects_blank_text() {
+    let mut state = get_initial_state();
+    let before = state.todos[1].description.clone();
+    reduce(&mut state, Action::EditTodo(2, "  ".to_string()));
+    assert!(state.failed);
+    assert!(state.output_buffer.starts_with("Usage: edit"));
+    assert_eq!(state.todos[1].description, before);
+}
+
+#[test]
+fn test_reduce_begin_edit_prefills_input() {
+    let mut state = get_initial_state();
+    state.todos[2].description = "call \"Sam\" -- today".to_string();
+    reduce(&mut state, Action::BeginEdit(3));
+    assert!(!state.dirty);
+    let action = parse_command(&state.input_buffer);
+    assert!(matches!(action, OneOrMany::One(Action::EditTodo(3, s)) if s == "call \"Sam\" -- today"));
+}
+
+#[test]
+fn test_parse_command_edit() {
+    assert!(matches!(parse_command("edit 2"), OneOrMany::One(Action::BeginEdit(2))));
+    let action = parse_command("edit 2 buy 3 eggs");
+    assert!(matches!(action, OneOrMany::One(Action::EditTodo(2, s)) if s == "buy 3 eggs"));
+    let action = parse_command("edit two eggs");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("column 6")));
+    let action = parse_command("edit");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.starts_with("Usage: edit")));
+}
+
+#[test]
+fn test_parse_command_undo_redo() {
+    assert!(matches!(parse_command("undo"), OneOrMany::One(Action::Undo)));
+    assert!(matches!(parse_command("redo"), OneOrMany::One(Action::Redo)));
+}
+
 #[test]
 fn test_reduce_remove_todo() {
     let mut state = get_initial_state();
@@ -130,8 +181,329 @@ fn test_parse_command_toggle_status_implicit() {
     }
 }
 
+#[test]
+fn test_parse_command_bare_backwards_range() {
+    let expected = "❌ At column 1: range 7-3 runs backwards, try 3-7.";
+    assert!(matches!(parse_command("7-3"), OneOrMany::One(Action::SetOutput(ref s)) if s == expected));
+    let expected = "❌ At column 6: range 7-3 runs backwards, try 3-7.";
+    assert!(matches!(parse_command("done 7-3"), OneOrMany::One(Action::SetOutput(ref s)) if s == expected));
+}
+
 #[test]
 fn test_parse_command_invalid() {
     let action = parse_command("invalid command");
     assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s == "Invalid command"));
 }
+
+#[test]
+fn test_reduce_tick_advances_finished_timer() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    let timer = state.todos[0].timer.as_ref().unwrap();
+    assert_eq!(timer.phase(), Phase::ShortBreak);
+    assert_eq!(state.todos[0].status, TodoStatus::InProgress);
+}
+
+#[test]
+fn test_reduce_tick_leaves_running_timer() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    assert_eq!(state.todos[0].timer.as_ref().unwrap().phase(), Phase::Work);
+}
+
+#[test]
+fn test_reduce_pause_and_resume_todo() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::PauseTodo(1));
+    assert!(state.todos[0].timer.as_ref().unwrap().is_paused());
+    assert_eq!(state.todos[0].status, TodoStatus::InProgress);
+    reduce(&mut state, Action::ResumeTodo(1));
+    assert!(!state.todos[0].timer.as_ref().unwrap().is_paused());
+}
+
+#[test]
+fn test_reduce_pause_without_timer() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::PauseTodo(2));
+    assert_eq!(state.output_buffer, "❌ Todo 2 has no running timer.");
+}
+
+#[test]
+fn test_parse_command_pause_resume() {
+    let actions = parse_command("pause 2");
+    assert!(matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::PauseTodo(2)])));
+    let actions = parse_command("resume 2");
+    assert!(matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::ResumeTodo(2)])));
+}
+
+#[test]
+fn test_reduce_start_todo_uses_cycle() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 3000, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    assert_eq!(state.todos[0].timer.as_ref().unwrap().duration(), 3000);
+    assert!(state.output_buffer.contains("Focus for 50 minutes!"));
+}
+
+#[test]
+fn test_reduce_start_todo_with_duration() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::StartTodoFor(1, 2700));
+    assert_eq!(state.todos[0].timer.as_ref().unwrap().duration(), 2700);
+    assert!(state.output_buffer.contains("Focus for 45 minutes!"));
+}
+
+#[test]
+fn test_reduce_start_todo_for_zero_seconds_fails() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::StartTodoFor(1, 0));
+    assert!(state.failed);
+    assert!(state.todos[0].timer.is_none());
+}
+
+#[test]
+fn test_parse_command_start_with_duration() {
+    let actions = parse_command("start 3 50m");
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::StartTodoFor(3, 3000)]))
+    );
+    let action = parse_command("start 3 later");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(_))));
+}
+
+#[test]
+fn test_reduce_tick_queues_one_notification() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    reduce(&mut state, Action::Tick);
+    let notifications: Vec<_> = state.effects
+        .iter()
+        .filter_map(|e| if let Effect::Notify(n) = e { Some(n) } else { None })
+        .collect();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].todo_id, 1);
+    assert!(state.banner.is_some());
+}
+
+#[test]
+fn test_reduce_tick_without_banner() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    state.notify.banner = false;
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    assert!(state.banner.is_none());
+    assert!(state.effects.iter().any(|e| matches!(e, Effect::Notify(_))));
+}
+
+#[test]
+fn test_reduce_dismiss_banner() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    reduce(&mut state, Action::DismissBanner);
+    assert!(state.banner.is_none());
+}
+
+fn running_timer(elapsed: u64) -> Timer {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let json = format!(r#"{{"duration":1500,"start_timestamp":{}}}"#, now - elapsed);
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_reduce_tick_records_finished_work_block() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    reduce(&mut state, Action::Tick);
+    assert!(
+        state.effects
+            .iter()
+            .any(|e| matches!(e, Effect::Record(r) if r.todo_id == 1 && r.outcome == Outcome::Finished))
+    );
+}
+
+#[test]
+fn test_reduce_stop_records_interruption() {
+    let mut state = get_initial_state();
+    state.todos[0].status = TodoStatus::InProgress;
+    state.todos[0].timer = Some(running_timer(60));
+    reduce(&mut state, Action::StopTodo(1));
+    assert!(
+        matches!(
+            &state.effects[..],
+            [Effect::Record(r)] if r.outcome == Outcome::Interrupted && r.duration == 60 && r.description == "todo 1"
+        )
+    );
+}
+
+#[test]
+fn test_reduce_complete_without_timer_records_nothing() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::CompleteTodo(1));
+    assert!(state.effects.is_empty());
+}
+
+#[test]
+fn test_reduce_toggle_stats() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::ToggleStats);
+    assert_eq!(state.view, View::Stats);
+    assert!(state.output_buffer.starts_with("📊"));
+    reduce(&mut state, Action::ToggleStats);
+    assert_eq!(state.view, View::Todos);
+}
+
+#[test]
+fn test_recorded_entries_feed_stats() {
+    let mut state = get_initial_state();
+    state.todos[0].status = TodoStatus::InProgress;
+    state.todos[0].timer = Some(running_timer(60));
+    reduce(&mut state, Action::StopTodo(1));
+    assert_eq!(state.history.len(), 1);
+    assert_eq!(state.history[0].todo_id, 1);
+}
+
+#[test]
+fn test_parse_command_add_keeps_word_order() {
+    let action = parse_command("add buy 2 apples");
+    assert!(matches!(action, OneOrMany::One(Action::AddTodo(s)) if s == "buy 2 apples"));
+    let action = parse_command("add \"Fix the main bug\"");
+    assert!(matches!(action, OneOrMany::One(Action::AddTodo(s)) if s == "Fix the main bug"));
+}
+
+#[test]
+fn test_parse_command_quoted_ids_are_text() {
+    let action = parse_command("rm 1 \"2\"");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("column 6") && s.contains("'2'")));
+    let action = parse_command("\"quit\"");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s == "Invalid command"));
+}
+
+#[test]
+fn test_parse_command_double_dash() {
+    let action = parse_command("add -- 42");
+    assert!(matches!(action, OneOrMany::One(Action::AddTodo(s)) if s == "42"));
+    let action = parse_command("start 3 -- 50m");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("duration")));
+}
+
+#[test]
+fn test_parse_command_reports_tokenizer_errors() {
+    let action = parse_command("add \"oops");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("column 5")));
+}
+
+#[test]
+fn test_parse_command_start_duration_in_any_position() {
+    let actions = parse_command("start 25m 1 2");
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::StartTodoFor(1, 1500), Action::StartTodoFor(2, 1500)]))
+    );
+    let action = parse_command("start 1 25m 5m");
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("'5m'")));
+}
+
+#[test]
+fn test_parse_command_for_selectors() {
+    let state = get_initial_state();
+    let actions = parse_command_for("rm all", &state.todos);
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::RemoveTodo(1), Action::RemoveTodo(2), Action::RemoveTodo(3)]))
+    );
+    let actions = parse_command_for("done 2-9", &state.todos);
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::CompleteTodo(2), Action::CompleteTodo(3)]))
+    );
+    let actions = parse_command_for("1-2", &state.todos);
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::ToggleStatus(1), Action::ToggleStatus(2)]))
+    );
+    let actions = parse_command_for("start \"/todo [13]/\" 10m", &state.todos);
+    assert!(
+        matches!(actions, OneOrMany::Many(ref a) if matches!(a[..], [Action::StartTodoFor(1, 600), Action::StartTodoFor(3, 600)]))
+    );
+}
+
+#[test]
+fn test_parse_command_for_reports_empty_selection() {
+    let state = get_initial_state();
+    let action = parse_command_for("rm completed", &state.todos);
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("No todos match 'completed'")));
+    let action = parse_command_for("rm 5-2", &state.todos);
+    assert!(matches!(action, OneOrMany::One(Action::SetOutput(s)) if s.contains("column 4")));
+}
+
+#[test]
+fn test_parse_command_stats() {
+    assert!(matches!(parse_command("stats"), OneOrMany::One(Action::ToggleStats)));
+}
+
+#[test]
+fn test_parse_command_list() {
+    assert!(matches!(parse_command("list"), OneOrMany::One(Action::ShowLists)));
+    assert!(matches!(parse_command("list init"), OneOrMany::One(Action::InitProjectList)));
+    assert!(matches!(parse_command("list new work"), OneOrMany::One(Action::NewList(n)) if n == "work"));
+    assert!(
+        matches!(parse_command("list switch work"), OneOrMany::One(Action::SwitchList(n)) if n == "work")
+    );
+    assert!(matches!(parse_command("list delete work"), OneOrMany::One(Action::SetOutput(_))));
+}
+
+#[test]
+fn test_reduce_new_list_queues_switch() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::NewList("work".to_string()));
+    assert!(
+        matches!(&state.effects[..], [Effect::SwitchList { name, create: true }] if name == "work")
+    );
+}
+
+#[test]
+fn test_reduce_new_list_rejects_bad_name() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::NewList("../x".to_string()));
+    assert!(state.effects.is_empty());
+    assert!(state.output_buffer.starts_with("❌"));
+}
+
+#[test]
+fn test_reduce_switch_list_rejects_paths() {
+    let mut state = get_initial_state();
+    for name in ["../../x", "a/b", "..", ""] {
+        reduce(&mut state, Action::SwitchList(name.to_string()));
+        assert!(state.effects.is_empty(), "{} was accepted", name);
+        assert!(state.output_buffer.starts_with("❌"));
+    }
+    reduce(&mut state, Action::SwitchList("project".to_string()));
+    reduce(&mut state, Action::SwitchList("global".to_string()));
+    assert_eq!(state.effects.len(), 2);
+}
+
+#[test]
+fn test_reduce_marks_state_dirty() {
+    let mut state = get_initial_state();
+    reduce(&mut state, Action::UpdateInput("add".to_string()));
+    reduce(&mut state, Action::Tick);
+    assert!(!state.dirty);
+    reduce(&mut state, Action::AddTodo("new".to_string()));
+    assert!(state.dirty);
+}
+
+#[test]
+fn test_reduce_tick_marks_dirty_on_phase_change() {
+    let mut state = get_initial_state();
+    state.cycle = Cycle { work: 0, ..Cycle::default() };
+    reduce(&mut state, Action::StartTodo(1));
+    state.dirty = false;
+    reduce(&mut state, Action::Tick);
+    assert!(state.dirty);
+}
@@ -0,0 +1,79 @@
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::selector::{ select, Selector, SelectorError };
+use pomonote::tokenizer::tokenize;
+
+fn todos() -> Vec<Todo> {
+    let mut todos = vec![
+        Todo::new(1, "Write parser +backend".to_string()),
+        Todo::new(2, "Fix login page +frontend".to_string()),
+        Todo::new(3, "Review +Backend tests".to_string()),
+        Todo::new(5, "Release notes".to_string())
+    ];
+    todos[1].status = TodoStatus::Completed;
+    todos[3].status = TodoStatus::Completed;
+    todos[2].status = TodoStatus::InProgress;
+    todos
+}
+
+fn pick(input: &str) -> Vec<u32> {
+    let selectors: Vec<Selector> = tokenize(input)
+        .unwrap()
+        .iter()
+        .map(|t| Selector::parse(t).unwrap())
+        .collect();
+    select(&selectors, &todos())
+}
+
+fn parse_error(input: &str) -> SelectorError {
+    Selector::parse(&tokenize(input).unwrap()[0]).unwrap_err()
+}
+
+#[cfg(test)]
+mod selector_tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_keep_order_and_missing_ids() {
+        assert_eq!(pick("3 1 9"), vec![3, 1, 9]);
+        assert_eq!(pick("1 1"), vec![1]);
+    }
+
+    #[test]
+    fn test_ranges_only_pick_existing_todos() {
+        assert_eq!(pick("2-7"), vec![2, 3, 5]);
+        assert_eq!(pick("4-4"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_all_and_status() {
+        assert_eq!(pick("all"), vec![1, 2, 3, 5]);
+        assert_eq!(pick("*"), vec![1, 2, 3, 5]);
+        assert_eq!(pick("completed"), vec![2, 5]);
+        assert_eq!(pick("done"), vec![2, 5]);
+        assert_eq!(pick("active"), vec![3]);
+        assert_eq!(pick("pending"), vec![1]);
+    }
+
+    #[test]
+    fn test_tags_ignore_case() {
+        assert_eq!(pick("+backend"), vec![1, 3]);
+        assert_eq!(pick("+frontend +backend"), vec![2, 1, 3]);
+        assert_eq!(pick("+back"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(pick("/^Re/"), vec![3, 5]);
+        assert_eq!(pick("\"/login page/\""), vec![2]);
+        assert_eq!(pick("/(?i)PARSER/"), vec![1]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse_error("7-3"), SelectorError::BackwardsRange(7, 3)));
+        assert!(matches!(parse_error("/[/"), SelectorError::BadPattern(_)));
+        assert!(matches!(parse_error("soon"), SelectorError::NotASelector));
+        assert!(matches!(parse_error("\"3\""), SelectorError::NotASelector));
+        assert!(matches!(parse_error("+"), SelectorError::NotASelector));
+    }
+}
@@ -0,0 +1,124 @@
+use pomonote::models::history::{ HistoryEntry, Outcome };
+use pomonote::stats::{ Stats, DAYS_SHOWN, WEEKS_SHOWN };
+use pomonote::utils::{ civil_date, day_at };
+
+const DAY: u64 = 86_400;
+// 2026-10-18 12:00 UTC, a Sunday
+const NOW: u64 = 20_744 * DAY + 12 * 3600;
+
+fn entry(todo_id: u32, days_ago: u64, outcome: Outcome) -> HistoryEntry {
+    let start = NOW - days_ago * DAY;
+    HistoryEntry {
+        todo_id,
+        description: format!("todo {}", todo_id),
+        start,
+        end: start + 1500,
+        duration: 1500,
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_history() {
+        let stats = Stats::from_history(&[], NOW);
+        assert_eq!(stats.per_day, vec![0; DAYS_SHOWN]);
+        assert_eq!(stats.per_week, vec![0; WEEKS_SHOWN]);
+        assert!(stats.per_todo.is_empty());
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.best_streak, 0);
+    }
+
+    #[test]
+    fn test_per_day_counts_only_finished() {
+        let entries = vec![
+            entry(1, 0, Outcome::Finished),
+            entry(1, 0, Outcome::Finished),
+            entry(2, 0, Outcome::Interrupted),
+            entry(2, 1, Outcome::Finished)
+        ];
+        let stats = Stats::from_history(&entries, NOW);
+        assert_eq!(stats.today, 2);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.per_day[DAYS_SHOWN - 1], 2);
+        assert_eq!(stats.per_day[DAYS_SHOWN - 2], 1);
+    }
+
+    #[test]
+    fn test_per_week_starts_on_monday() {
+        // NOW is a Sunday: six days ago is this week's Monday, seven days ago is last Sunday
+        let entries = vec![entry(1, 6, Outcome::Finished), entry(1, 7, Outcome::Finished)];
+        let stats = Stats::from_history(&entries, NOW);
+        assert_eq!(stats.per_week[WEEKS_SHOWN - 1], 1);
+        assert_eq!(stats.per_week[WEEKS_SHOWN - 2], 1);
+    }
+
+    #[test]
+    fn test_per_todo_includes_interrupted_time() {
+        let entries = vec![
+            entry(1, 0, Outcome::Finished),
+            entry(2, 0, Outcome::Finished),
+            entry(2, 1, Outcome::Interrupted)
+        ];
+        let stats = Stats::from_history(&entries, NOW);
+        assert_eq!(stats.per_todo[0], (2, "todo 2".to_string(), 3000));
+        assert_eq!(stats.per_todo[1], (1, "todo 1".to_string(), 1500));
+        assert_eq!(stats.focused_seconds, 4500);
+    }
+
+    #[test]
+    fn test_streaks() {
+        let entries = vec![
+            entry(1, 1, Outcome::Finished),
+            entry(1, 2, Outcome::Finished),
+            entry(1, 5, Outcome::Finished),
+            entry(1, 6, Outcome::Finished),
+            entry(1, 7, Outcome::Finished)
+        ];
+        let stats = Stats::from_history(&entries, NOW);
+        // Nothing finished today yet, but yesterday's run still counts
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.best_streak, 3);
+    }
+
+    #[test]
+    fn test_streak_broken() {
+        let entries = vec![entry(1, 2, Outcome::Finished)];
+        assert_eq!(Stats::from_history(&entries, NOW).current_streak, 0);
+    }
+
+    #[test]
+    fn test_days_roll_over_at_local_midnight() {
+        // 20:00 UTC yesterday is already today ten hours east of UTC, and still
+        // the day before five hours west
+        let late = HistoryEntry { start: NOW - 16 * 3600, ..entry(1, 0, Outcome::Finished) };
+        let east = Stats::from_history_by(std::slice::from_ref(&late), NOW, |secs| day_at(secs, 10 * 3600));
+        assert_eq!(east.today, 1);
+        let west = Stats::from_history_by(&[late], NOW, |secs| day_at(secs, -5 * 3600));
+        assert_eq!(west.today, 0);
+        assert_eq!(west.per_day[DAYS_SHOWN - 2], 1);
+    }
+
+    #[test]
+    fn test_day_at() {
+        assert_eq!(day_at(NOW, 0), 20_744);
+        assert_eq!(day_at(NOW, 12 * 3600), 20_745);
+        assert_eq!(day_at(NOW, -13 * 3600), 20_743);
+    }
+
+    #[test]
+    fn test_day_label() {
+        assert_eq!(Stats::day_label(NOW, 0), "10-18");
+        assert_eq!(Stats::day_label(NOW, 18), "09-30");
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(20_744), (2026, 10, 18));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+    }
+}
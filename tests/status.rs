@@ -0,0 +1,72 @@
+use pomonote::models::cycle::{ Cycle, Phase };
+use pomonote::models::timer::Timer;
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::status::{ self, Waybar, DEFAULT_TEMPLATE };
+
+fn in_progress(id: u32, description: &str, timer: Timer) -> Todo {
+    let mut todo = Todo::new(id, description.to_string());
+    todo.status = TodoStatus::InProgress;
+    todo.timer = Some(timer);
+    todo
+}
+
+fn running() -> Timer {
+    let mut timer = Timer::new();
+    timer.start();
+    timer
+}
+
+#[cfg(test)]
+mod status_tests {
+    use super::*;
+
+    #[test]
+    fn test_active_prefers_running_timer() {
+        let mut paused = running();
+        paused.pause();
+        let todos = vec![
+            Todo::new(1, "idle".to_string()),
+            in_progress(2, "paused", paused),
+            in_progress(3, "running", running())
+        ];
+        assert_eq!(status::active(&todos).unwrap().id, 3);
+        assert_eq!(status::active(&todos[..2]).unwrap().id, 2);
+        assert!(status::active(&todos[..1]).is_none());
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        let todo = in_progress(4, "Fix parser", Timer::new());
+        assert_eq!(status::render(DEFAULT_TEMPLATE, &todo), "🍅 25:00 Fix parser");
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let timer = Timer::for_phase(Phase::ShortBreak, 2, &Cycle::default());
+        let todo = in_progress(7, "Review", timer);
+        let line = status::render("{icon} #{id} {phase} {round} {seconds}s {unknown}", &todo);
+        assert_eq!(line, "☕ #7 Short Break 2 300s {unknown}");
+    }
+
+    #[test]
+    fn test_render_leaves_placeholders_in_description() {
+        let todo = in_progress(7, "Explain {id} and {round}", Timer::new());
+        assert_eq!(status::render("{id}: {description} {phase}", &todo), "7: Explain {id} and {round} Work");
+    }
+
+    #[test]
+    fn test_waybar_idle_and_running() {
+        let idle = Waybar::new(None, String::new());
+        assert_eq!(idle.class, "idle");
+        assert_eq!(idle.percentage, 0);
+
+        let todo = in_progress(1, "Write", running());
+        let waybar = Waybar::new(Some(&todo), "🍅 25:00 Write".to_string());
+        assert_eq!(waybar.class, "work");
+        assert!(waybar.percentage <= 1);
+        assert_eq!(waybar.tooltip, "#1 Write (Work, round 1)");
+
+        let json = serde_json::to_value(&waybar).unwrap();
+        assert_eq!(json["text"], "🍅 25:00 Write");
+    }
+}
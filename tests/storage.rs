@@ -0,0 +1,274 @@
+mod common;
+
+use pomonote::app;
+use pomonote::models::history::HistoryEntry;
+use pomonote::models::todo::{ LoadError, Todo };
+use pomonote::reduce::AppState;
+use pomonote::storage;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+fn temp_dir(name: &str) -> PathBuf {
+    common::temp_dir("storage", name)
+}
+
+fn state_for(path: &Path) -> AppState {
+    let mut state = AppState::new(Todo::load_all(path).unwrap());
+    state.file = path.to_path_buf();
+    state.disk_stamp = storage::stamp(path);
+    state.saved = Todo::load_file(path).unwrap();
+    state
+}
+
+#[cfg(test)]
+mod storage_tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_explicit_file() {
+        let file = PathBuf::from("/tmp/elsewhere.json");
+        let project = PathBuf::from("/repo/.pomonote.json");
+        assert_eq!(storage::resolve(Some(file.clone()), Some(&project)), file);
+    }
+
+    #[test]
+    fn test_save_creates_parent_dirs() {
+        let dir = temp_dir("nested");
+        let path = dir.join("a/b/todos.json");
+        let todos = vec![Todo::new(1, "Nested".to_string())];
+        Todo::save_all(&path, &todos).unwrap();
+
+        let loaded = Todo::load_all(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].description, "Nested");
+    }
+
+    #[test]
+    fn test_migrate_legacy_moves_todos_and_history() {
+        let dir = temp_dir("migrate");
+        let legacy = dir.join("todos.json");
+        let target = dir.join("data/todos.json");
+        Todo::save_all(&legacy, &[Todo::new(7, "Old".to_string())]).unwrap();
+        fs::write(HistoryEntry::path_for(&legacy), "").unwrap();
+
+        let moved = fs::canonicalize(&legacy).unwrap();
+        assert_eq!(storage::migrate_legacy(&legacy, &target).unwrap(), Some(moved));
+        assert!(!legacy.exists());
+        assert!(HistoryEntry::path_for(&target).exists());
+        assert_eq!(Todo::load_all(&target).unwrap()[0].id, 7);
+    }
+
+    #[test]
+    fn test_migrate_legacy_keeps_existing_target() {
+        let dir = temp_dir("existing");
+        let legacy = dir.join("todos.json");
+        let target = dir.join("data/todos.json");
+        Todo::save_all(&legacy, &[Todo::new(1, "Old".to_string())]).unwrap();
+        Todo::save_all(&target, &[Todo::new(2, "New".to_string())]).unwrap();
+
+        assert_eq!(storage::migrate_legacy(&legacy, &target).unwrap(), None);
+        assert!(legacy.exists());
+        assert_eq!(Todo::load_all(&target).unwrap()[0].id, 2);
+    }
+
+    #[test]
+    fn test_migrate_legacy_skips_other_todos_files() {
+        let dir = temp_dir("foreign");
+        let legacy = dir.join("todos.json");
+        let target = dir.join("data/todos.json");
+        fs::write(&legacy, r#"{"items": [{"title": "not ours"}]}"#).unwrap();
+
+        assert_eq!(storage::migrate_legacy(&legacy, &target).unwrap(), None);
+        assert!(legacy.exists());
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_migrate_legacy_without_legacy_file() {
+        let dir = temp_dir("none");
+        assert_eq!(storage::migrate_legacy(&dir.join("todos.json"), &dir.join("data/todos.json")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_contents() {
+        let dir = temp_dir("atomic");
+        let path = dir.join("todos.json");
+        storage::write_atomic(&path, b"first").unwrap();
+        storage::write_atomic(&path, b"second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        let leftovers: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(leftovers.len(), 1);
+    }
+
+    #[test]
+    fn test_rotate_backups_keeps_newest() {
+        let dir = temp_dir("backups");
+        let path = dir.join("todos.json");
+        for version in 1..=5 {
+            fs::write(&path, format!("v{}", version)).unwrap();
+            storage::rotate_backups(&path, 3).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(storage::backup_path(&path, 1)).unwrap(), "v5");
+        assert_eq!(fs::read_to_string(storage::backup_path(&path, 2)).unwrap(), "v4");
+        assert_eq!(fs::read_to_string(storage::backup_path(&path, 3)).unwrap(), "v3");
+        assert!(!storage::backup_path(&path, 4).exists());
+    }
+
+    #[test]
+    fn test_rotate_backups_without_file() {
+        let dir = temp_dir("no-backup");
+        storage::rotate_backups(&dir.join("todos.json"), 3).unwrap();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = temp_dir("load-missing");
+        assert!(Todo::load_all(&dir.join("todos.json")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_corrupt_file_is_error() {
+        let dir = temp_dir("load-corrupt");
+        let path = dir.join("todos.json");
+        fs::write(&path, "[{\"id\": 1,}]").unwrap();
+        let error = Todo::load_all(&path).unwrap_err();
+        assert!(matches!(error, LoadError::Parse(_)));
+        assert!(error.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn test_load_or_quarantine_moves_corrupt_file() {
+        let dir = temp_dir("quarantine");
+        let path = dir.join("todos.json");
+        fs::write(&path, "not json").unwrap();
+
+        let (stored, warning) = storage::load_or_quarantine(&path).unwrap();
+        assert!(stored.todos.is_empty());
+        assert!(warning.unwrap().contains("corrupt"));
+        assert!(!path.exists());
+        let moved: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("todos.json.corrupt-"))
+            .collect();
+        assert_eq!(moved.len(), 1);
+        assert_eq!(fs::read_to_string(moved[0].path()).unwrap(), "not json");
+    }
+
+    #[test]
+    fn test_load_or_quarantine_unreadable_blocks_saving() {
+        let dir = temp_dir("unreadable");
+        // A directory where the file should be can't be read as a list
+        let path = dir.join("todos.json");
+        fs::create_dir_all(&path).unwrap();
+        assert!(storage::load_or_quarantine(&path).is_err());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_load_or_quarantine_valid_file() {
+        let dir = temp_dir("valid");
+        let path = dir.join("todos.json");
+        Todo::save_all(&path, &[Todo::new(1, "Fine".to_string())]).unwrap();
+        let (stored, warning) = storage::load_or_quarantine(&path).unwrap();
+        assert_eq!(stored.todos.len(), 1);
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let dir = temp_dir("lock");
+        let path = dir.join("todos.json");
+        let lock = storage::Lock::acquire(&path).unwrap();
+        assert_eq!(storage::lock_path(&path), dir.join("todos.json.lock"));
+
+        match storage::Lock::acquire(&path) {
+            Err(storage::LockError::Held(pid)) => assert_eq!(pid, Some(std::process::id())),
+            other => panic!("expected the lock to be held, got {:?}", other),
+        }
+
+        drop(lock);
+        assert!(storage::Lock::acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn test_stamp_changes_on_write() {
+        let dir = temp_dir("stamp");
+        let path = dir.join("todos.json");
+        assert!(storage::stamp(&path).is_none());
+
+        Todo::save_all(&path, &[]).unwrap();
+        let before = storage::stamp(&path);
+        assert!(before.is_some());
+        Todo::save_all(&path, &[Todo::new(1, "Changed".to_string())]).unwrap();
+        assert_ne!(storage::stamp(&path), before);
+    }
+
+    #[test]
+    fn test_reload_picks_up_external_change() {
+        let dir = temp_dir("reload");
+        let path = dir.join("todos.json");
+        Todo::save_all(&path, &[Todo::new(1, "Mine".to_string())]).unwrap();
+        let mut state = state_for(&path);
+
+        app::reload_if_changed(&mut state);
+        assert!(state.output_buffer.is_empty());
+
+        Todo::save_all(&path, &[Todo::new(1, "Mine".to_string()), Todo::new(2, "Theirs".to_string())]).unwrap();
+        app::reload_if_changed(&mut state);
+        assert_eq!(state.todos.len(), 2);
+        assert!(state.output_buffer.contains("Reloaded"));
+    }
+
+    #[test]
+    fn test_reload_merges_unsaved_changes() {
+        let dir = temp_dir("reload-merge");
+        let path = dir.join("todos.json");
+        Todo::save_all(&path, &[]).unwrap();
+        let mut state = state_for(&path);
+        state.todos.push(Todo::new(1, "Unsaved".to_string()));
+        state.dirty = true;
+
+        Todo::save_all(&path, &[Todo::new(1, "Theirs".to_string())]).unwrap();
+        app::reload_if_changed(&mut state);
+        let descriptions: Vec<&str> = state.todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        assert_eq!(descriptions, vec!["Theirs", "Unsaved"]);
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn test_reload_keeps_local_edits_and_removals() {
+        let dir = temp_dir("reload-local");
+        let path = dir.join("todos.json");
+        Todo::save_all(&path, &[Todo::new(1, "Edit me".to_string()), Todo::new(2, "Remove me".to_string())]).unwrap();
+        let mut state = state_for(&path);
+        state.todos = vec![Todo::new(1, "Edited".to_string())];
+        state.dirty = true;
+
+        let theirs = [Todo::new(1, "Edit me".to_string()), Todo::new(2, "Remove me".to_string()), Todo::new(3, "Theirs".to_string())];
+        Todo::save_all(&path, &theirs).unwrap();
+        app::reload_if_changed(&mut state);
+        let descriptions: Vec<&str> = state.todos
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        assert_eq!(descriptions, vec!["Edited", "Theirs"]);
+    }
+
+    #[test]
+    fn test_reload_ignores_deleted_file() {
+        let dir = temp_dir("reload-deleted");
+        let path = dir.join("todos.json");
+        Todo::save_all(&path, &[Todo::new(1, "Keep".to_string())]).unwrap();
+        let mut state = state_for(&path);
+        fs::remove_file(&path).unwrap();
+        app::reload_if_changed(&mut state);
+        assert_eq!(state.todos.len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 use pomonote::models::timer::Timer;
 use std::thread;
-use std::time::Duration;
+use std::time::{ Duration, SystemTime, UNIX_EPOCH };
 
 #[cfg(test)]
 mod timer_tests {
@@ -55,4 +55,47 @@ mod timer_tests {
         let after = timer.remaining_seconds();
         assert!(after < initial);
     }
+
+    #[test]
+    fn test_timer_pause_freezes_countdown() {
+        let mut timer = Timer::new();
+        assert!(!timer.pause());
+        timer.start();
+        assert!(timer.pause());
+        assert!(timer.is_paused());
+        let frozen = timer.remaining_seconds();
+        thread::sleep(Duration::from_millis(1100));
+        assert_eq!(timer.remaining_seconds(), frozen);
+        assert!(!timer.pause());
+    }
+
+    #[test]
+    fn test_timer_resume_keeps_elapsed_time() {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let json = format!(
+            r#"{{"duration":1500,"start_timestamp":{},"paused_at":{}}}"#,
+            now - 100,
+            now - 40
+        );
+        let mut timer: Timer = serde_json::from_str(&json).unwrap();
+        assert!(timer.is_paused());
+        assert_eq!(timer.remaining_seconds(), 1440);
+
+        assert!(timer.resume());
+        assert!(!timer.is_paused());
+        let remaining = timer.remaining_seconds();
+        assert!((1439..=1440).contains(&remaining));
+        assert!(!timer.resume());
+    }
+
+    #[test]
+    fn test_paused_timer_survives_round_trip() {
+        let mut timer = Timer::new();
+        timer.start();
+        timer.pause();
+        let json = serde_json::to_string(&timer).unwrap();
+        let restored: Timer = serde_json::from_str(&json).unwrap();
+        assert!(restored.is_paused());
+        assert_eq!(restored.remaining_seconds(), timer.remaining_seconds());
+    }
 }
@@ -105,6 +105,7 @@ mod edge_case_tests {
     }
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_timer_immediately_after_start() {
         let mut timer = Timer::new();
         timer.start();
@@ -112,4 +113,47 @@ mod edge_case_tests {
         let remaining = timer.remaining_seconds();
         assert!(remaining >= 1499 && remaining <= 1500);
     }
+
+    #[test]
+    fn test_merge_keeps_changes_from_both_sides() {
+        let base = vec![
+            Todo::new(1, "Shared".to_string()),
+            Todo::new(2, "Edited here".to_string()),
+            Todo::new(3, "Removed here".to_string()),
+            Todo::new(4, "Removed there".to_string())
+        ];
+        let mut done = Todo::new(1, "Shared".to_string());
+        done.status = TodoStatus::Completed;
+        let disk = vec![
+            done,
+            Todo::new(2, "Edited here".to_string()),
+            Todo::new(3, "Removed here".to_string()),
+            Todo::new(5, "From disk".to_string())
+        ];
+        let local = vec![
+            Todo::new(1, "Shared".to_string()),
+            Todo::new(2, "Edited locally".to_string()),
+            Todo::new(4, "Removed there".to_string()),
+            Todo::new(5, "Typed here".to_string())
+        ];
+
+        let merged = Todo::merge(&base, disk, &local);
+        let summary: Vec<(u32, &str)> = merged
+            .iter()
+            .map(|t| (t.id, t.description.as_str()))
+            .collect();
+        assert_eq!(summary, vec![(1, "Shared"), (2, "Edited locally"), (5, "From disk"), (6, "Typed here")]);
+        assert_eq!(merged[0].status, TodoStatus::Completed);
+    }
+
+    #[test]
+    fn test_merge_keeps_local_edit_of_todo_removed_on_disk() {
+        let base = vec![Todo::new(1, "Draft".to_string())];
+        let mut started = Todo::new(1, "Draft".to_string());
+        started.status = TodoStatus::InProgress;
+
+        let merged = Todo::merge(&base, Vec::new(), &[started]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].status, TodoStatus::InProgress);
+    }
 }
@@ -0,0 +1,96 @@
+use pomonote::reduce::{ parse_command, Action };
+use pomonote::tokenizer::{ escape, tokenize, Token, TokenError };
+use pomonote::utils::OneOrMany;
+
+fn texts(input: &str) -> Vec<String> {
+    tokenize(input)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.text)
+        .collect()
+}
+
+#[cfg(test)]
+mod tokenizer_tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_on_whitespace() {
+        assert_eq!(texts("  add  buy 2   apples "), vec!["add", "buy", "2", "apples"]);
+        assert!(tokenize("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_columns_and_literals() {
+        let tokens = tokenize("rm \"3\" 4").unwrap();
+        assert_eq!(tokens[0], Token { text: "rm".to_string(), column: 1, literal: false });
+        assert_eq!(tokens[1], Token { text: "3".to_string(), column: 4, literal: true });
+        assert_eq!(tokens[2], Token { text: "4".to_string(), column: 8, literal: false });
+    }
+
+    #[test]
+    fn test_quotes() {
+        assert_eq!(texts("add \"Fix the main bug\""), vec!["add", "Fix the main bug"]);
+        assert_eq!(texts("add 'say \"hi\"'"), vec!["add", "say \"hi\""]);
+        assert_eq!(texts("add pre\"fix\"ed"), vec!["add", "prefixed"]);
+        assert_eq!(texts("add \"\""), vec!["add", ""]);
+    }
+
+    #[test]
+    fn test_quoted_blank_text_is_not_a_todo() {
+        for input in ["add \"\"", "add \"   \"", "add '' ''"] {
+            let usage = matches!(parse_command(input), OneOrMany::One(Action::SetOutput(ref s)) if s == "Usage: add <todo text>");
+            assert!(usage, "{} was accepted", input);
+        }
+        for input in ["edit 1 \"\"", "edit 1 \" \""] {
+            let usage = matches!(parse_command(input), OneOrMany::One(Action::SetOutput(ref s)) if s.starts_with("Usage: edit"));
+            assert!(usage, "{} was accepted", input);
+        }
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(texts(r"add it\'s done"), vec!["add", "it's", "done"]);
+        assert_eq!(texts(r"add two\ words"), vec!["add", "two words"]);
+        assert_eq!(texts(r#"add "a \"quoted\" \\ word \n""#), vec!["add", r#"a "quoted" \ word \n"#]);
+    }
+
+    #[test]
+    fn test_escape_round_trips() {
+        for text in [r#"say "hi" to Sam's \ team"#, "a -- b", "--", "x--y --z"] {
+            let tokens = tokenize(&escape(text)).unwrap();
+            let words: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+            assert_eq!(words.join(" "), text);
+        }
+        assert_eq!(escape("plain words"), "plain words");
+    }
+
+    #[test]
+    fn test_double_dash_makes_the_rest_literal() {
+        let tokens = tokenize("add -- 42 -- x").unwrap();
+        let summary: Vec<(&str, bool)> = tokens
+            .iter()
+            .map(|t| (t.text.as_str(), t.literal))
+            .collect();
+        assert_eq!(summary, vec![("add", false), ("42", true), ("--", true), ("x", true)]);
+
+        assert_eq!(texts(r"add \-- '--'"), vec!["add", "--", "--"]);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_problem() {
+        let error = tokenize("add \"unfinished").unwrap_err();
+        assert_eq!(error, TokenError::UnterminatedQuote { quote: '"', column: 5 });
+        assert_eq!(error.column(), 5);
+        assert_eq!(error.to_string(), "unterminated \" at column 5");
+
+        assert_eq!(tokenize("add don't").unwrap_err(), TokenError::UnterminatedQuote { quote: '\'', column: 8 });
+        assert_eq!(tokenize("add oops\\").unwrap_err(), TokenError::TrailingEscape { column: 9 });
+    }
+
+    #[test]
+    fn test_columns_count_characters() {
+        let tokens = tokenize("add 🍅 done").unwrap();
+        assert_eq!(tokens[2].column, 7);
+    }
+}
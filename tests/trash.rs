@@ -0,0 +1,157 @@
+mod common;
+
+use common::state_with;
+use pomonote::config::Config;
+use pomonote::models::todo::{ Todo, TodoStatus, Trashed };
+use pomonote::reduce::{ reduce, parse_command, Action };
+use pomonote::utils::OneOrMany;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str) -> PathBuf {
+    common::temp_dir("trash", name).join("todos.json")
+}
+
+fn trashed(id: u32, deleted_at: u64) -> Trashed {
+    Trashed { todo: Todo::new(id, format!("old {}", id)), deleted_at }
+}
+
+#[cfg(test)]
+mod trash_tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_moves_to_trash() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::StartTodo(2));
+        reduce(&mut state, Action::RemoveTodo(2));
+        assert_eq!(state.todos.len(), 2);
+        assert_eq!(state.trash.len(), 1);
+        let todo = &state.trash[0].todo;
+        assert_eq!(todo.id, 2);
+        assert_eq!(todo.status, TodoStatus::Pending);
+        assert!(todo.timer.is_none());
+    }
+
+    #[test]
+    fn test_restore_puts_todo_back_in_order() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::RemoveTodo(2));
+        reduce(&mut state, Action::RestoreTodo(2));
+        assert!(!state.failed);
+        assert!(state.trash.is_empty());
+        let ids: Vec<u32> = state.todos.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_restore_renumbers_taken_id() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::RemoveTodo(3));
+        reduce(&mut state, Action::AddTodo("new 3".to_string()));
+        reduce(&mut state, Action::RestoreTodo(3));
+        assert_eq!(state.todos[3].id, 4);
+        assert_eq!(state.todos[3].description, "todo 3");
+        assert!(state.output_buffer.contains("restored as 4"));
+    }
+
+    #[test]
+    fn test_restore_unknown_id_fails() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::RestoreTodo(1));
+        assert!(state.failed);
+        assert_eq!(state.todos.len(), 3);
+    }
+
+    #[test]
+    fn test_show_and_empty_trash() {
+        let mut state = state_with(3);
+        reduce(&mut state, Action::ShowTrash);
+        assert!(state.output_buffer.contains("empty"));
+
+        reduce(&mut state, Action::RemoveTodo(1));
+        reduce(&mut state, Action::RemoveTodo(3));
+        reduce(&mut state, Action::ShowTrash);
+        assert!(state.output_buffer.contains("#1 todo 1 (removed today)"));
+        assert!(state.output_buffer.contains("#3 todo 3"));
+
+        reduce(&mut state, Action::EmptyTrash);
+        assert!(state.trash.is_empty());
+        assert!(state.output_buffer.contains("2 todos"));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let day = 86400;
+        let mut state = state_with(3);
+        state.trash = vec![trashed(7, 0), trashed(8, 25 * day)];
+        state.trash_days = 30;
+        state.expire_trash(31 * day);
+        assert_eq!(state.trash.len(), 1);
+        assert_eq!(state.trash[0].todo.id, 8);
+        assert!(state.dirty);
+
+        state.trash_days = 0;
+        state.expire_trash(1000 * day);
+        assert_eq!(state.trash.len(), 1);
+    }
+
+    #[test]
+    fn test_huge_trash_days_never_expire() {
+        let mut state = state_with(3);
+        state.trash = vec![trashed(7, 0)];
+        state.trash_days = u64::MAX;
+        state.expire_trash(100 * 365 * 86400);
+        assert_eq!(state.trash.len(), 1);
+    }
+
+    #[test]
+    fn test_file_keeps_trash_and_reads_old_format() {
+        let path = temp_file("format");
+        Todo::save_file(&path, &[Todo::new(1, "live".to_string())], &[trashed(2, 100)]).unwrap();
+        let stored = Todo::load_file(&path).unwrap();
+        assert_eq!(stored.todos.len(), 1);
+        assert_eq!(stored.trash[0].todo.id, 2);
+        assert_eq!(stored.trash[0].deleted_at, 100);
+
+        fs::write(&path, r#"[{"id":4,"description":"legacy","status":"Pending","timer":null}]"#).unwrap();
+        let stored = Todo::load_file(&path).unwrap();
+        assert_eq!(stored.todos[0].id, 4);
+        assert!(stored.trash.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_local_trash() {
+        let merged = Trashed::merge(&[trashed(1, 10)], vec![trashed(1, 10), trashed(3, 30)], &[trashed(1, 10), trashed(2, 20)]);
+        let ids: Vec<u32> = merged.iter().map(|t| t.todo.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_merge_keeps_restored_and_emptied_trash_gone() {
+        let base = [trashed(1, 10), trashed(2, 20)];
+        // Emptied locally while the other side trashed something new
+        let merged = Trashed::merge(&base, vec![trashed(1, 10), trashed(2, 20), trashed(3, 30)], &[]);
+        let ids: Vec<u32> = merged.iter().map(|t| t.todo.id).collect();
+        assert_eq!(ids, vec![3]);
+
+        // Restored on the other side
+        let merged = Trashed::merge(&base, vec![trashed(2, 20)], &base);
+        let ids: Vec<u32> = merged.iter().map(|t| t.todo.id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_config_trash_days() {
+        assert_eq!(Config::default().trash_days, 30);
+        assert_eq!(Config::parse(r#"{"trash_days": 7}"#).unwrap().trash_days, 7);
+    }
+
+    #[test]
+    fn test_parse_trash_commands() {
+        assert!(matches!(parse_command("trash"), OneOrMany::One(Action::ShowTrash)));
+        assert!(matches!(parse_command("empty-trash"), OneOrMany::One(Action::EmptyTrash)));
+        let action = parse_command("restore 2 5");
+        assert!(matches!(&action, OneOrMany::Many(a) if matches!(a[..], [Action::RestoreTodo(2), Action::RestoreTodo(5)])));
+    }
+}
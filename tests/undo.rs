@@ -0,0 +1,144 @@
+mod common;
+
+use common::state_with;
+use crossterm::event::{ KeyCode, KeyEvent, KeyModifiers };
+use pomonote::app;
+use pomonote::editor::Edit;
+use pomonote::models::cycle::Phase;
+use pomonote::models::timer::Timer;
+use pomonote::models::todo::{ Todo, TodoStatus };
+use pomonote::reduce::{ reduce, Action, AppState };
+use pomonote::undo::{ UndoStack, Version, UNDO_LIMIT };
+use pomonote::utils::OneOrMany;
+
+fn ids(state: &AppState) -> Vec<u32> {
+    state.todos
+        .iter()
+        .map(|t| t.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod undo_tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_undo_and_redo() {
+        let mut stack = UndoStack::default();
+        let (mut todos, mut trash) = (Vec::new(), Vec::new());
+        let before = Version { todos: vec![Todo::new(1, "a".to_string())], trash: Vec::new() };
+        stack.record("rm 1", before, Version::default());
+
+        assert_eq!(stack.undo(&mut todos, &mut trash), Some("rm 1".to_string()));
+        assert_eq!(todos.len(), 1);
+        assert!(!stack.can_undo());
+        assert_eq!(stack.redo(&mut todos, &mut trash), Some("rm 1".to_string()));
+        assert!(todos.is_empty());
+        assert_eq!(stack.redo(&mut todos, &mut trash), None);
+    }
+
+    #[test]
+    fn test_new_change_drops_redo() {
+        let mut stack = UndoStack::default();
+        let (mut todos, mut trash) = (Vec::new(), Vec::new());
+        stack.record("add a", Version::default(), Version::default());
+        stack.undo(&mut todos, &mut trash);
+        assert!(stack.can_redo());
+        stack.record("add b", Version::default(), Version::default());
+        assert!(!stack.can_redo());
+    }
+
+    #[test]
+    fn test_stack_is_bounded() {
+        let mut stack = UndoStack::default();
+        let (mut todos, mut trash) = (Vec::new(), Vec::new());
+        for i in 0..UNDO_LIMIT + 5 {
+            stack.record(&format!("add {}", i), Version::default(), Version::default());
+        }
+        let mut steps = 0;
+        while stack.undo(&mut todos, &mut trash).is_some() {
+            steps += 1;
+        }
+        assert_eq!(steps, UNDO_LIMIT);
+    }
+
+    #[test]
+    fn test_batch_undoes_as_one_step() {
+        let mut state = state_with(3);
+        let actions = vec![Action::RemoveTodo(1), Action::RemoveTodo(2), Action::RemoveTodo(3)];
+        app::apply_all(&mut state, "rm 1 2 3", actions);
+        assert!(state.todos.is_empty());
+
+        assert_eq!(state.trash.len(), 3);
+
+        app::apply_all(&mut state, "undo", vec![Action::Undo]);
+        assert_eq!(ids(&state), vec![1, 2, 3]);
+        assert!(state.trash.is_empty());
+        assert_eq!(state.output_buffer, "↩️  Undid 'rm 1 2 3'.");
+
+        app::apply_all(&mut state, "redo", vec![Action::Redo]);
+        assert!(state.todos.is_empty());
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn test_failed_and_read_only_commands_are_not_recorded() {
+        let mut state = state_with(3);
+        app::apply_all(&mut state, "done 9", vec![Action::CompleteTodo(9)]);
+        app::apply_all(&mut state, "stats", vec![Action::ToggleStats]);
+        assert!(!state.undo.can_undo());
+
+        reduce(&mut state, Action::Undo);
+        assert!(state.failed);
+        assert_eq!(state.output_buffer, "❌ Nothing to undo.");
+    }
+
+    #[test]
+    fn test_undo_restores_status() {
+        let mut state = state_with(3);
+        app::apply_all(&mut state, "done 2", vec![Action::CompleteTodo(2)]);
+        assert_eq!(state.todos[1].status, TodoStatus::Completed);
+        reduce(&mut state, Action::Undo);
+        assert_eq!(state.todos[1].status, TodoStatus::Pending);
+    }
+
+    #[test]
+    fn test_undo_leaves_running_timers_alone() {
+        let mut state = state_with(3);
+        app::apply_all(&mut state, "start 1", vec![Action::StartTodo(1)]);
+        app::apply_all(&mut state, "edit 1 renamed", vec![Action::EditTodo(1, "renamed".to_string())]);
+        app::apply_all(&mut state, "done 2", vec![Action::CompleteTodo(2)]);
+
+        // The work block ends and a break starts while the commands sit on the stack
+        let mut timer = Timer::new().with_duration(0);
+        timer.start();
+        state.todos[0].timer = Some(timer);
+        reduce(&mut state, Action::Tick);
+        assert_eq!(state.todos[0].timer.as_ref().unwrap().phase(), Phase::ShortBreak);
+
+        reduce(&mut state, Action::Undo);
+        reduce(&mut state, Action::Undo);
+        assert_eq!(state.todos[0].description, "todo 1");
+        assert_eq!(state.todos[0].timer.as_ref().unwrap().phase(), Phase::ShortBreak);
+        assert_eq!(state.todos[1].status, TodoStatus::Pending);
+
+        reduce(&mut state, Action::Redo);
+        assert_eq!(state.todos[0].description, "renamed");
+        assert_eq!(state.todos[0].timer.as_ref().unwrap().phase(), Phase::ShortBreak);
+
+        // Undoing the start itself does take the timer away
+        reduce(&mut state, Action::Undo);
+        reduce(&mut state, Action::Undo);
+        assert!(state.todos[0].timer.is_none());
+    }
+
+    #[test]
+    fn test_ctrl_keys() {
+        let state = state_with(3);
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert!(matches!(app::handle_key_event(ctrl('z'), &state), OneOrMany::One(Action::Undo)));
+        assert!(matches!(app::handle_key_event(ctrl('y'), &state), OneOrMany::One(Action::Redo)));
+        let plain = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
+        assert!(matches!(app::handle_key_event(plain, &state), OneOrMany::One(Action::EditInput(Edit::Insert('z')))));
+    }
+}